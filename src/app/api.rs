@@ -0,0 +1,642 @@
+use crate::app::state::DsqApp;
+use crate::app::ui::main_tab::{start_executable, start_preset};
+use crate::core::history::ExitReason;
+use crate::core::lint::{check_executable, check_path};
+use crate::platform::update::VERSION;
+use crate::shared::types::ApiConfig;
+use eframe::egui;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// Archivo con el token, junto a config.json
+pub const API_TOKEN_FILE: &str = "api_token.txt";
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+/// Conexiones atendidas a la vez; las demás reciben 503
+const MAX_CONNECTIONS: usize = 8;
+
+/// Comando que la interfaz ejecuta en su propio hilo
+pub enum ApiCommand {
+    Status,
+    ListPresets,
+    ListSessions,
+    Start(StartRequest),
+    Stop(Option<u64>),
+}
+
+#[derive(Deserialize)]
+pub struct StartRequest {
+    preset: Option<String>,
+    executable: Option<String>,
+    path: Option<String>,
+    duration_minutes: Option<u64>,
+}
+
+pub struct ApiResponse {
+    status: u16,
+    body: Value,
+}
+
+impl ApiResponse {
+    fn new(status: u16, body: Value) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::new(status, json!({ "error": message.into() }))
+    }
+}
+
+pub struct ApiRequest {
+    command: ApiCommand,
+    reply: mpsc::Sender<ApiResponse>,
+}
+
+/// Inicia el servidor en segundo plano y devuelve el canal por el que
+/// llegan los comandos
+pub fn start_server(
+    config: &ApiConfig,
+    ctx: egui::Context,
+) -> Result<mpsc::Receiver<ApiRequest>, Box<dyn std::error::Error>> {
+    let token = load_or_create_token()?;
+    let (sender, receiver) = mpsc::channel();
+    let server = Arc::new(Server {
+        token,
+        sender,
+        ctx,
+        connections: AtomicUsize::new(0),
+    });
+
+    #[cfg(unix)]
+    if let Some(socket) = &config.unix_socket {
+        remove_stale_socket(socket)?;
+        let listener = std::os::unix::net::UnixListener::bind(socket)?;
+        log::info!("Control API listening on unix socket {}", socket);
+        std::thread::spawn(move || server.serve(listener.incoming()));
+        return Ok(receiver);
+    }
+
+    #[cfg(not(unix))]
+    if config.unix_socket.is_some() {
+        log::warn!("Unix sockets are not supported on this platform, using HTTP");
+    }
+
+    let listener = std::net::TcpListener::bind(("127.0.0.1", config.port))?;
+    log::info!("Control API listening on http://127.0.0.1:{}", config.port);
+    std::thread::spawn(move || server.serve(listener.incoming()));
+    Ok(receiver)
+}
+
+/// Borra el socket que dejó una ejecución anterior. Cualquier otro tipo
+/// de archivo en esa ruta se deja intacto y se informa como error.
+#[cfg(unix)]
+fn remove_stale_socket(path: &str) -> std::io::Result<()> {
+    use std::io::{Error, ErrorKind};
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Err(Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists and is not a socket", path),
+        )),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Conexión que acepta el servidor, por TCP o socket Unix
+trait ApiStream: Read + Write + Send + 'static {
+    fn set_timeouts(&self, timeout: Duration) -> std::io::Result<()>;
+}
+
+impl ApiStream for std::net::TcpStream {
+    fn set_timeouts(&self, timeout: Duration) -> std::io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+#[cfg(unix)]
+impl ApiStream for std::os::unix::net::UnixStream {
+    fn set_timeouts(&self, timeout: Duration) -> std::io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+/// Corta la lectura si el pedido completo tarda más que `IO_TIMEOUT`,
+/// aunque el cliente envíe de a un byte
+struct DeadlineReader<R> {
+    inner: R,
+    deadline: Instant,
+}
+
+impl<R: Read> Read for DeadlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if Instant::now() >= self.deadline {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "request timed out",
+            ));
+        }
+        self.inner.read(buf)
+    }
+}
+
+/// Lee el token o crea uno nuevo si no existe
+fn load_or_create_token() -> std::io::Result<String> {
+    if let Ok(token) = std::fs::read_to_string(API_TOKEN_FILE) {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+
+    let token = generate_token()?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // El archivo se crea ya con permisos de solo el usuario, sin un
+    // momento en que otros puedan leerlo
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // Un archivo vacío de antes puede tener otros permisos
+        if std::path::Path::new(API_TOKEN_FILE).exists() {
+            std::fs::set_permissions(API_TOKEN_FILE, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(API_TOKEN_FILE)?.write_all(token.as_bytes())?;
+    log::info!("Created control API token in {}", API_TOKEN_FILE);
+    Ok(token)
+}
+
+/// Token aleatorio de 256 bits tomado del generador del sistema
+fn generate_token() -> std::io::Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)?;
+    Ok(hex::encode(bytes))
+}
+
+/// Compara sin cortar en el primer byte distinto
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+struct Server {
+    token: String,
+    sender: mpsc::Sender<ApiRequest>,
+    ctx: egui::Context,
+    /// Conexiones en curso, para limitar los hilos
+    connections: AtomicUsize,
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Server {
+    /// Atiende cada conexión en su propio hilo, así un cliente que no
+    /// envía nada no bloquea a los demás
+    fn serve<S: ApiStream>(self: Arc<Self>, incoming: impl Iterator<Item = std::io::Result<S>>) {
+        for stream in incoming.flatten() {
+            if let Err(e) = stream.set_timeouts(IO_TIMEOUT) {
+                log::warn!("Failed to set control API timeouts: {}", e);
+                continue;
+            }
+
+            if self.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                self.connections.fetch_sub(1, Ordering::SeqCst);
+                log::warn!("Rejected control API connection: too many open connections");
+                write_response(stream, ApiResponse::error(503, "too many connections"));
+                continue;
+            }
+
+            let server = Arc::clone(&self);
+            std::thread::spawn(move || {
+                server.handle_connection(stream);
+                server.connections.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    }
+
+    fn handle_connection<S: Read + Write>(&self, mut stream: S) {
+        let mut reader = DeadlineReader {
+            inner: &mut stream,
+            deadline: Instant::now() + IO_TIMEOUT,
+        };
+        let response = match read_request(&mut reader) {
+            Ok(request) => self.respond(request),
+            Err(e) => ApiResponse::error(400, e),
+        };
+        write_response(stream, response);
+    }
+
+    fn respond(&self, request: HttpRequest) -> ApiResponse {
+        let authorized = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token_matches(&self.token, token.trim()));
+        if !authorized {
+            log::warn!("Rejected control API request without a valid token");
+            return ApiResponse::error(401, "missing or invalid token");
+        }
+
+        let command = match parse_command(&request) {
+            Ok(command) => command,
+            Err(response) => return response,
+        };
+        log::debug!("Control API {} {}", request.method, request.path);
+
+        // La interfaz ejecuta el comando en su hilo y responde por el canal
+        let (reply, receiver) = mpsc::channel();
+        if self.sender.send(ApiRequest { command, reply }).is_err() {
+            return ApiResponse::error(503, "application is shutting down");
+        }
+        self.ctx.request_repaint();
+
+        receiver
+            .recv_timeout(IO_TIMEOUT)
+            .unwrap_or_else(|_| ApiResponse::error(503, "application did not respond"))
+    }
+}
+
+fn parse_command(request: &HttpRequest) -> Result<ApiCommand, ApiResponse> {
+    let path = request.path.trim_end_matches('/');
+    match (request.method.as_str(), path) {
+        ("GET", "/status") => Ok(ApiCommand::Status),
+        ("GET", "/presets") => Ok(ApiCommand::ListPresets),
+        ("GET", "/sessions") => Ok(ApiCommand::ListSessions),
+        ("POST", "/sessions") => serde_json::from_slice(&request.body)
+            .map(ApiCommand::Start)
+            .map_err(|e| ApiResponse::error(400, format!("invalid body: {}", e))),
+        ("DELETE", "/sessions") => Ok(ApiCommand::Stop(None)),
+        ("DELETE", _) if path.starts_with("/sessions/") => path["/sessions/".len()..]
+            .parse()
+            .map(|id| ApiCommand::Stop(Some(id)))
+            .map_err(|_| ApiResponse::error(400, "invalid session id")),
+        _ => Err(ApiResponse::error(404, "not found")),
+    }
+}
+
+fn read_request<S: Read>(stream: &mut S) -> Result<HttpRequest, String> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|e| e.to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err("malformed request line".into());
+    };
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).map_err(|e| e.to_string())?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => {
+                content_length = value.trim().parse().map_err(|_| "invalid content-length")?
+            }
+            "authorization" => authorization = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err("request body too large".into());
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+
+    Ok(HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        authorization,
+        body,
+    })
+}
+
+fn write_response<S: Write>(mut stream: S, response: ApiResponse) {
+    let body = response.body.to_string();
+    let reply = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        body.len(),
+        body
+    );
+    if let Err(e) = stream.write_all(reply.as_bytes()) {
+        log::warn!("Failed to write control API response: {}", e);
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+/// Ejecuta los comandos pendientes de la API en el hilo de la interfaz
+pub fn handle_api_requests(app: &mut DsqApp) {
+    let Some(receiver) = &app.api_requests else {
+        return;
+    };
+    let requests: Vec<ApiRequest> = receiver.try_iter().collect();
+
+    for request in requests {
+        let response = execute(app, request.command);
+        let _ = request.reply.send(response);
+    }
+}
+
+fn execute(app: &mut DsqApp, command: ApiCommand) -> ApiResponse {
+    match command {
+        ApiCommand::Status => ApiResponse::new(
+            200,
+            json!({
+                "version": VERSION,
+                "discord_running": app.discord_running_cache,
+                "discord_ipc": app
+                    .discord_detection
+                    .sockets
+                    .iter()
+                    .map(|socket| json!({
+                        "socket": socket.path.display().to_string(),
+                        "client": socket.owner.as_ref().map(|client| &client.name),
+                    }))
+                    .collect::<Vec<_>>(),
+                "rich_presence_connected": app
+                    .rich_presence
+                    .as_ref()
+                    .is_some_and(|rp| rp.is_connected()),
+                "current_game": app.current_simulated_game,
+                "active_sessions": active_sessions(app).len(),
+                "queue_pending": app.queue.pending.len(),
+                "status": app.status,
+            }),
+        ),
+        ApiCommand::ListPresets => ApiResponse::new(200, json!(app.presets)),
+        ApiCommand::ListSessions => ApiResponse::new(200, Value::Array(active_sessions(app))),
+        ApiCommand::Start(request) => start_session(app, request),
+        ApiCommand::Stop(Some(id)) => {
+            let exists = app
+                .process_monitor
+                .active_sessions()
+                .iter()
+                .any(|session| session.group_id == id);
+            if !exists {
+                return ApiResponse::error(404, format!("session {} not found", id));
+            }
+            app.process_monitor.stop_group(id, ExitReason::Manual);
+            ApiResponse::new(202, json!({ "stopping": [id] }))
+        }
+        ApiCommand::Stop(None) => {
+            let ids: Vec<Value> = active_sessions(app)
+                .iter()
+                .map(|session| session["id"].clone())
+                .collect();
+            app.process_monitor.stop_all(ExitReason::Manual);
+            ApiResponse::new(202, json!({ "stopping": ids }))
+        }
+    }
+}
+
+/// Sesiones activas en formato JSON
+fn active_sessions(app: &DsqApp) -> Vec<Value> {
+    app.process_monitor
+        .active_sessions()
+        .into_iter()
+        .map(|session| {
+            json!({
+                "id": session.group_id,
+                "preset": session.preset_name,
+                "executables": session.executables,
+                "started_at": session.started_at.to_rfc3339(),
+                "duration_minutes": session.duration_minutes,
+                "remaining_seconds": session.remaining().as_secs(),
+            })
+        })
+        .collect()
+}
+
+fn start_session(app: &mut DsqApp, request: StartRequest) -> ApiResponse {
+    let group_id = match (&request.preset, &request.executable) {
+        (Some(name), _) => {
+            let Some(index) = app
+                .presets
+                .iter()
+                .position(|preset| preset.name.eq_ignore_ascii_case(name))
+            else {
+                return ApiResponse::error(404, format!("preset {} not found", name));
+            };
+            let minutes = request.duration_minutes.unwrap_or_else(|| {
+                app.presets[index]
+                    .duration_minutes
+                    .unwrap_or(app.default_duration_minutes)
+            });
+            start_preset(app, index, minutes)
+        }
+        (None, Some(executable)) => {
+            let path = request.path.as_deref().unwrap_or_default();
+            if let Some(problem) = check_executable(executable).or_else(|| check_path(path)) {
+                return ApiResponse::error(400, problem);
+            }
+            let minutes = request
+                .duration_minutes
+                .unwrap_or(app.default_duration_minutes);
+            start_executable(app, executable, path, minutes)
+        }
+        (None, None) => {
+            return ApiResponse::error(400, "either preset or executable is required");
+        }
+    };
+
+    match group_id {
+        Some(id) => ApiResponse::new(201, json!({ "id": id })),
+        None => ApiResponse::error(500, app.status.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::in_temp_dir;
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+
+    const TOKEN: &str = "test-token";
+
+    /// Servidor en un puerto libre; los comandos quedan en el canal
+    fn spawn_server() -> (SocketAddr, mpsc::Receiver<ApiRequest>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let server = Arc::new(Server {
+            token: TOKEN.to_string(),
+            sender,
+            ctx: egui::Context::default(),
+            connections: AtomicUsize::new(0),
+        });
+        std::thread::spawn(move || server.serve(listener.incoming()));
+        (address, receiver)
+    }
+
+    /// Envía un pedido HTTP y devuelve el código y el cuerpo
+    fn send(address: SocketAddr, request: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1;
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    /// Atiende los comandos con una app real, como lo hace la interfaz
+    fn spawn_app(receiver: mpsc::Receiver<ApiRequest>) {
+        std::thread::spawn(move || {
+            let mut app = DsqApp::default();
+            app.api_requests = Some(receiver);
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while Instant::now() < deadline {
+                handle_api_requests(&mut app);
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        });
+    }
+
+    #[test]
+    fn server_checks_token_end_to_end() {
+        let (address, receiver) = spawn_server();
+        spawn_app(receiver);
+        let authorization = format!("Authorization: Bearer {}\r\n", TOKEN);
+
+        let (status, body) = send(
+            address,
+            &format!("GET /status HTTP/1.1\r\n{}\r\n", authorization),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["version"], VERSION);
+
+        let (status, body) = send(
+            address,
+            "GET /status HTTP/1.1\r\nAuthorization: Bearer wrong-token\r\n\r\n",
+        );
+        assert_eq!(status, 401);
+        assert!(body["error"].is_string());
+
+        let (status, _) = send(address, "GET /presets HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 401);
+
+        let (status, _) = send(
+            address,
+            &format!("GET /unknown HTTP/1.1\r\n{}\r\n", authorization),
+        );
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn server_rejects_path_traversal() {
+        let (address, receiver) = spawn_server();
+        spawn_app(receiver);
+
+        let body = r#"{"executable": "victim.txt", "path": "../../"}"#;
+        let (status, response) = send(
+            address,
+            &format!(
+                "POST /sessions HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+                TOKEN,
+                body.len(),
+                body
+            ),
+        );
+        assert_eq!(status, 400);
+        assert!(response["error"].as_str().unwrap().contains(".."));
+    }
+
+    #[test]
+    fn idle_client_does_not_block_others() {
+        let (address, _receiver) = spawn_server();
+        let _idle = TcpStream::connect(address).unwrap();
+
+        let started = Instant::now();
+        let (status, _) = send(address, "GET /status HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 401);
+        assert!(started.elapsed() < IO_TIMEOUT);
+    }
+
+    #[test]
+    fn token_is_random_and_private() {
+        in_temp_dir(|_| {
+            let token = load_or_create_token().unwrap();
+            assert_eq!(token.len(), 64);
+            assert!(token.bytes().all(|b| b.is_ascii_hexdigit()));
+            assert_ne!(token, generate_token().unwrap());
+            assert_eq!(load_or_create_token().unwrap(), token);
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = std::fs::metadata(API_TOKEN_FILE).unwrap().permissions().mode();
+                assert_eq!(mode & 0o777, 0o600);
+            }
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_only_replaces_sockets() {
+        in_temp_dir(|_| {
+            let config = |path: &str| ApiConfig {
+                enabled: true,
+                port: 0,
+                unix_socket: Some(path.to_string()),
+            };
+
+            std::fs::write("notes.txt", "keep").unwrap();
+            assert!(start_server(&config("notes.txt"), egui::Context::default()).is_err());
+            assert_eq!(std::fs::read_to_string("notes.txt").unwrap(), "keep");
+
+            std::os::unix::fs::symlink("notes.txt", "link.sock").unwrap();
+            assert!(start_server(&config("link.sock"), egui::Context::default()).is_err());
+            assert_eq!(std::fs::read_to_string("notes.txt").unwrap(), "keep");
+
+            // El socket de una ejecución anterior se reemplaza
+            drop(std::os::unix::net::UnixListener::bind("api.sock").unwrap());
+            assert!(start_server(&config("api.sock"), egui::Context::default()).is_ok());
+        });
+    }
+}
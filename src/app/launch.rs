@@ -0,0 +1,126 @@
+use crate::app::state::DsqApp;
+use crate::app::translate::translate;
+use crate::app::ui::main_tab::start_preset;
+use crate::platform::clients::ClientRegistry;
+use crate::platform::discord::{find_ipc_sockets, open_discord, DiscordInstall};
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+/// Tiempo máximo de espera para que Discord abra su socket IPC
+pub const DISCORD_READY_TIMEOUT: Duration = Duration::from_secs(90);
+/// Cada cuánto se busca el socket mientras se espera
+const READY_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Discord abierto desde la app que todavía no está listo
+pub struct DiscordLaunch {
+    pub client_name: String,
+    pub started: Instant,
+    last_probe: Instant,
+}
+
+impl DiscordLaunch {
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+impl DsqApp {
+    /// Abre Discord y empieza a esperar a que acepte conexiones
+    pub fn launch_discord(&mut self, install: &DiscordInstall) {
+        if let Err(e) = open_discord(install) {
+            log::error!("Failed to open Discord: {}", e);
+            self.status = translate(self, "discord_launch_failed").replace("{error}", &e.to_string());
+            return;
+        }
+
+        log::info!("Waiting for {} to be ready", install.client.name);
+        let now = Instant::now();
+        self.discord_launch = Some(DiscordLaunch {
+            client_name: install.client.name.clone(),
+            started: now,
+            last_probe: now,
+        });
+    }
+
+    pub fn cancel_discord_launch(&mut self) {
+        if self.discord_launch.take().is_some() {
+            log::info!("Stopped waiting for Discord");
+        }
+    }
+
+    /// Busca el socket IPC del Discord que se está abriendo y, cuando
+    /// aparece, conecta Rich Presence y, si se pidió, inicia el preset
+    pub fn poll_discord_launch(&mut self, ctx: &egui::Context) {
+        let Some(launch) = &mut self.discord_launch else {
+            return;
+        };
+
+        // Seguir revisando aunque no haya eventos en la ventana
+        ctx.request_repaint_after(READY_PROBE_INTERVAL);
+        if launch.last_probe.elapsed() < READY_PROBE_INTERVAL {
+            return;
+        }
+        launch.last_probe = Instant::now();
+
+        // Sirve un socket del cliente abierto o uno de dueño desconocido
+        let registry = ClientRegistry::new(&self.custom_clients);
+        let table = self.process_monitor.process_table();
+        let ready = find_ipc_sockets(&registry, table).iter().any(|socket| {
+            socket
+                .owner
+                .as_ref()
+                .is_none_or(|owner| owner.name == launch.client_name)
+        });
+
+        if ready {
+            let launch = self.discord_launch.take().unwrap();
+            log::info!(
+                "{} ready after {}s",
+                launch.client_name,
+                launch.elapsed().as_secs()
+            );
+            self.on_discord_ready(&launch);
+        } else if launch.elapsed() >= DISCORD_READY_TIMEOUT {
+            let launch = self.discord_launch.take().unwrap();
+            log::warn!(
+                "{} was not ready after {}s",
+                launch.client_name,
+                DISCORD_READY_TIMEOUT.as_secs()
+            );
+            self.status = translate(self, "discord_launch_timeout")
+                .replace("{name}", &launch.client_name)
+                .replace("{seconds}", &DISCORD_READY_TIMEOUT.as_secs().to_string());
+        }
+    }
+
+    fn on_discord_ready(&mut self, launch: &DiscordLaunch) {
+        let was_running = self.discord_was_running;
+        let sessions_before = self.process_monitor.active_sessions().len();
+
+        // Actualizar la detección ya, sin esperar a la próxima verificación
+        self.invalidate_discord_cache();
+        self.poll_discord();
+
+        // Si no se notó la apertura, Rich Presence puede seguir esperando
+        // su próximo reintento
+        if was_running != Some(false) {
+            if let Some(rp) = &self.rich_presence {
+                rp.reconnect();
+            }
+        }
+
+        self.status = translate(self, "discord_ready").replace("{name}", &launch.client_name);
+
+        // La automatización al abrirse Discord pudo haber iniciado una sesión
+        let auto_started = self.process_monitor.active_sessions().len() > sessions_before;
+        if self.launch_start_preset && !auto_started && self.selected_preset < self.presets.len() {
+            let index = self.selected_preset;
+            let minutes = self.presets[index]
+                .duration_minutes
+                .unwrap_or(self.default_duration_minutes);
+            log::info!("Starting {} because Discord is ready", self.presets[index].name);
+            start_preset(self, index, minutes);
+        }
+    }
+}
+
@@ -1,3 +1,6 @@
+pub mod api;
+pub mod launch;
+pub mod presence;
 pub mod state;
 pub mod ui;
 pub mod translate;
@@ -0,0 +1,122 @@
+use crate::app::state::DsqApp;
+use crate::app::translate::translate;
+use crate::core::process::ActiveSession;
+use crate::shared::richpresence::template::{
+    effective_client_id, ActivityContent, ActivityVars, MultiSessionDisplay,
+};
+use crate::shared::richpresence::RichPresenceWorker;
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+/// Cada cuánto se actualizan las plantillas con tiempo transcurrido o restante.
+/// Discord limita las actualizaciones, así que no conviene bajar de 15 s.
+pub const PRESENCE_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+impl DsqApp {
+    /// Actividad de Rich Presence para el estado actual de la app
+    pub fn presence_activity(&self) -> ActivityContent {
+        let sessions = self.process_monitor.active_sessions();
+        let template = &self.rich_presence_template;
+
+        let vars = match (sessions.last(), template.multi_session) {
+            (Some(_), MultiSessionDisplay::Aggregate) if sessions.len() > 1 => {
+                let first = &sessions[0];
+                let last_end = sessions.iter().map(session_end).max();
+                ActivityVars {
+                    preset: None,
+                    game: Some(
+                        translate(self, "rp_games_count")
+                            .replace("{count}", &sessions.len().to_string()),
+                    ),
+                    elapsed_seconds: first.elapsed().as_secs(),
+                    remaining_seconds: sessions.iter().map(|s| s.remaining().as_secs()).max(),
+                    sessions: sessions.len(),
+                    start_timestamp: Some(first.started_at.timestamp()),
+                    end_timestamp: last_end,
+                }
+            }
+            (Some(latest), _) => ActivityVars {
+                game: self.current_simulated_game.clone(),
+                preset: Some(latest.preset_name.clone()),
+                elapsed_seconds: latest.elapsed().as_secs(),
+                remaining_seconds: Some(latest.remaining().as_secs()),
+                sessions: sessions.len(),
+                start_timestamp: Some(latest.started_at.timestamp()),
+                end_timestamp: Some(session_end(latest)),
+            },
+            (None, _) => ActivityVars {
+                game: self.current_simulated_game.clone(),
+                ..Default::default()
+            },
+        };
+
+        template.render(&vars, |key| translate(self, key))
+    }
+
+    /// Envía la actividad actual al hilo de Rich Presence, si está activo
+    pub fn update_rich_presence(&mut self) {
+        if let Some(rp) = &self.rich_presence {
+            rp.set_activity(self.presence_activity());
+        }
+    }
+
+    /// Inicia o detiene el hilo de Rich Presence según la configuración
+    pub fn sync_rich_presence(&mut self, ctx: &egui::Context) {
+        let client_id = effective_client_id(&self.rich_presence_client_id);
+
+        // Con otra aplicación de Discord hay que volver a conectar
+        if let Some(mut rp) = self
+            .rich_presence
+            .take_if(|rp| rp.client_id() != client_id)
+        {
+            log::info!("Rich Presence application changed, reconnecting");
+            rp.shutdown();
+        }
+
+        match (self.rich_presence_enabled, self.rich_presence.take()) {
+            (true, None) => {
+                log::info!("Starting Rich Presence with application {}", client_id);
+                let ctx = ctx.clone();
+                self.rich_presence = Some(RichPresenceWorker::spawn(client_id, move || {
+                    ctx.request_repaint()
+                }));
+                self.update_rich_presence();
+            }
+            (false, Some(mut rp)) => {
+                log::info!("Stopping Rich Presence");
+                rp.shutdown();
+            }
+            (_, rp) => self.rich_presence = rp,
+        }
+    }
+
+    /// Indica si la actividad cambia con el tiempo y hay que refrescarla
+    pub fn presence_needs_refresh(&self) -> bool {
+        self.rich_presence.is_some()
+            && self.current_simulated_game.is_some()
+            && self
+                .rich_presence_template
+                .is_time_dependent(|key| translate(self, key))
+    }
+
+    /// Refresca periódicamente la actividad mientras hay un juego simulado
+    pub fn refresh_rich_presence(&mut self) {
+        if !self.presence_needs_refresh() {
+            return;
+        }
+        if self
+            .last_presence_refresh
+            .is_some_and(|last| last.elapsed() < PRESENCE_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_presence_refresh = Some(Instant::now());
+
+        self.update_rich_presence();
+    }
+}
+
+/// Cierre programado de una sesión, en segundos Unix
+fn session_end(session: &ActiveSession) -> i64 {
+    session.started_at.timestamp() + session.duration_minutes as i64 * 60
+}
@@ -1,42 +1,139 @@
+use crate::app::api::{handle_api_requests, ApiRequest};
+use crate::app::launch::DiscordLaunch;
+use crate::app::presence::PRESENCE_REFRESH_INTERVAL;
 use crate::app::ui::render_ui;
-use crate::core::process::ProcessMonitor;
-use crate::shared::richpresence::RichPresenceManager;
-use crate::shared::types::{LangMap, Preset};
+use crate::core::presets::load_presets;
+use crate::app::translate::translate;
+use crate::app::ui::main_tab::start_preset;
+use crate::app::ui::settings_tab::save_settings_config;
+use crate::core::history::{append_history, ExitReason, SessionRecord};
+use crate::core::hooks::SessionHook;
+use crate::core::process::{FinishedSession, ProcessMonitor};
+use crate::core::queue::SimulationQueue;
+use crate::core::scheduler::{
+    merge_last_runs, take_due_runs, MissedRunPolicy, Repeat, ScheduledRun, SchedulerLock,
+};
+use crate::core::quests::{record_session, save_quests, Quest};
+use crate::core::schema::PresetParseError;
+use crate::shared::config::load_config;
+use crate::shared::richpresence::template::ActivityTemplate;
+use crate::shared::richpresence::RichPresenceWorker;
+use crate::platform::clients::ClientRegistry;
+use crate::platform::discord::{detect_discord, find_discord_installs, DiscordDetection, DiscordInstall};
+use crate::shared::types::{ApiConfig, AutoStartRule, CustomClient, LangMap, Preset, PresetExecutable};
+use chrono::{Local, Weekday};
 use eframe::{egui, App};
 use std::collections::HashMap;
+use std::sync::mpsc::Receiver;
 use std::time::{Duration, Instant};
 
 #[derive(Default)]
 pub struct DsqApp {
     pub process_name: String,
     pub custom_path: String,
+    pub duration_minutes: u64,
+    pub default_duration_minutes: u64,
     pub status: String,
     pub presets: Vec<Preset>,
     pub filtered_presets: Vec<Preset>,
+    pub preset_errors: Vec<PresetParseError>,
+    pub preset_tag_filter: Vec<String>,
     pub selected_preset: usize,
     pub langs: HashMap<String, LangMap>,
     pub selected_lang: String,
     pub presets_outdated: bool,
     pub selected_tab: Tab,
     pub rich_presence_enabled: bool,
-    pub rich_presence: Option<RichPresenceManager>,
+    pub rich_presence: Option<RichPresenceWorker>,
     pub current_simulated_game: Option<String>,
+    pub rich_presence_template: ActivityTemplate,
+    /// ID de la aplicación de Discord; vacío usa el de DSQProcess
+    pub rich_presence_client_id: String,
+    /// Texto del campo de ID, se aplica al validarlo
+    pub client_id_input: String,
+    pub new_preset_image: String,
+    pub last_presence_refresh: Option<Instant>,
 
     // Gestión de presets
     pub show_add_preset_dialog: bool,
     pub show_edit_preset_dialog: bool,
     pub show_delete_confirmation: bool,
     pub new_preset_name: String,
-    pub new_preset_executable: String,
-    pub new_preset_path: String,
+    pub new_preset_executables: Vec<PresetExecutable>,
+    pub new_preset_category: String,
+    pub new_preset_tags: String,
+    pub new_preset_args: String,
+    pub new_preset_cwd: String,
+    pub new_preset_env: String,
+    pub new_preset_duration: String,
     pub preset_to_delete: Option<String>,
     pub preset_to_edit: Option<String>,
 
+    // Importación de aplicaciones detectables de Discord
+    pub show_import_dialog: bool,
+    pub import_file_path: String,
+    pub import_to_official: bool,
+
+    // Seguimiento de misiones
+    pub quests: Vec<Quest>,
+    /// Error al leer `quests.json`; mientras exista no se guarda
+    /// para no reemplazar el archivo del usuario
+    pub quests_load_error: Option<String>,
+    /// Error al leer `config.json`; mientras exista no se guarda
+    pub config_load_error: Option<String>,
+    /// Hay un `scheduler run` atendiendo las programaciones
+    pub headless_scheduler: bool,
+    /// Configuración que se guardó por última vez, para no reescribir
+    /// config.json en cada cuadro si nada cambió
+    pub saved_config: String,
+    pub new_quest_preset: String,
+    pub new_quest_minutes: u64,
+    pub new_quest_expires: String,
+    pub new_quest_notes: String,
+
+    // Cola de simulaciones
+    pub queue: SimulationQueue,
+
+    // Simulaciones programadas
+    pub schedules: Vec<ScheduledRun>,
+    pub new_schedule_minutes: u64,
+    pub new_schedule_repeat: Repeat,
+    pub new_schedule_date: String,
+    pub new_schedule_weekday: Option<Weekday>,
+    pub new_schedule_time: String,
+    pub new_schedule_missed: MissedRunPolicy,
+    pub last_schedule_check: Option<Instant>,
+
+    // Historial de sesiones
+    pub session_history: Vec<SessionRecord>,
+
     // Cache para optimización de Discord
     pub discord_running_cache: Option<bool>,
-    pub discord_versions_cache: Option<Vec<crate::platform::discord::DiscordVersion>>,
+    pub discord_installs_cache: Option<Vec<DiscordInstall>>,
+    /// Sockets IPC y clientes encontrados en la última verificación
+    pub discord_detection: DiscordDetection,
+    /// Último estado de Discord observado, para detectar cuándo se abre o se cierra
+    pub discord_was_running: Option<bool>,
+    /// Discord abierto desde la app mientras se espera a que esté listo
+    pub discord_launch: Option<DiscordLaunch>,
+    /// Iniciar el preset seleccionado cuando Discord esté listo
+    pub launch_start_preset: bool,
+
+    // Automatización al abrirse Discord
+    pub auto_start: AutoStartRule,
+    pub last_used_preset: Option<String>,
     pub last_discord_check: Option<Instant>,
 
+    // API local
+    pub api: ApiConfig,
+
+    /// Hooks de sesión; se editan en config.json
+    pub hooks: Vec<SessionHook>,
+
+    /// Clientes de Discord agregados por el usuario
+    pub custom_clients: Vec<CustomClient>,
+    pub api_requests: Option<Receiver<ApiRequest>>,
+
     // Monitor de procesos
     pub process_monitor: ProcessMonitor,
     pub last_process_check: Option<Instant>,
@@ -46,11 +143,36 @@ pub struct DsqApp {
 pub enum Tab {
     #[default]
     Main,
+    Quests,
+    Stats,
     Settings,
     About,
 }
 
 impl DsqApp {
+    /// Recarga los presets desde disco, conservando los errores de parseo
+    pub fn reload_presets(&mut self) {
+        let loaded = load_presets();
+        self.presets = loaded.presets;
+        self.filtered_presets = self.presets.clone();
+        self.preset_errors = loaded.errors;
+
+        if self.selected_preset >= self.presets.len() {
+            self.selected_preset = 0;
+        }
+    }
+
+    /// Preset cuyo ejecutable principal coincide con la configuración actual
+    pub fn configured_preset(&self) -> Option<&Preset> {
+        let process_name = self.process_name.trim();
+        self.presets.iter().find(|preset| {
+            preset.primary().map(|exe| exe.for_host()).is_some_and(|exe| {
+                exe.executable.eq_ignore_ascii_case(process_name)
+                    && exe.path.eq_ignore_ascii_case(self.custom_path.trim())
+            })
+        })
+    }
+
     /// Verifica si debe actualizar el cache de Discord
     pub fn should_check_discord(&mut self) -> bool {
         const CHECK_INTERVAL: Duration = Duration::from_secs(5);
@@ -71,10 +193,96 @@ impl DsqApp {
         }
     }
 
+    /// Actualiza el estado de Discord y aplica la automatización
+    /// cuando Discord se abre o se cierra
+    pub fn poll_discord(&mut self) {
+        if !self.should_check_discord() {
+            return;
+        }
+
+        let registry = ClientRegistry::new(&self.custom_clients);
+        let detection = detect_discord(&registry, self.process_monitor.process_table());
+        let running = detection.is_running();
+        if detection != self.discord_detection {
+            for socket in &detection.sockets {
+                log::info!(
+                    "Discord IPC socket {} owned by {}",
+                    socket.path.display(),
+                    socket.owner.as_ref().map_or("unknown client", |client| &client.name)
+                );
+            }
+        }
+        self.discord_detection = detection;
+        self.discord_running_cache = Some(running);
+        if !running {
+            self.discord_installs_cache = Some(find_discord_installs(&registry));
+        }
+
+        match (self.discord_was_running.replace(running), running) {
+            (Some(false), true) => self.on_discord_started(),
+            (Some(true), false) => self.on_discord_stopped(),
+            _ => {}
+        }
+    }
+
+    fn on_discord_started(&mut self) {
+        log::info!("Discord started");
+
+        // La conexión anterior quedó inválida al cerrarse Discord
+        if let Some(rp) = &self.rich_presence {
+            rp.reconnect();
+        }
+
+        if !self.auto_start.enabled {
+            return;
+        }
+
+        let Some(preset_name) = self
+            .auto_start
+            .preset
+            .clone()
+            .or_else(|| self.last_used_preset.clone())
+        else {
+            log::warn!("Auto-start enabled but there is no preset to start");
+            return;
+        };
+
+        match self
+            .presets
+            .iter()
+            .position(|preset| preset.name.eq_ignore_ascii_case(&preset_name))
+        {
+            Some(index) => {
+                let minutes = self.presets[index]
+                    .duration_minutes
+                    .unwrap_or(self.default_duration_minutes);
+                log::info!("Auto-starting {} because Discord started", preset_name);
+                if start_preset(self, index, minutes).is_some() {
+                    self.status = translate(self, "auto_started").replace("{name}", &preset_name);
+                }
+            }
+            None => {
+                log::warn!("Auto-start preset not found: {}", preset_name);
+                self.status = translate(self, "preset_not_found").replace("{name}", &preset_name);
+            }
+        }
+    }
+
+    fn on_discord_stopped(&mut self) {
+        log::info!("Discord stopped");
+
+        if self.auto_start.stop_on_discord_exit {
+            log::info!("Stopping all simulations because Discord exited");
+            self.queue.running = false;
+            self.process_monitor.stop_all(ExitReason::Manual);
+            self.status = translate(self, "auto_stopped");
+        }
+    }
+
     /// Invalida el cache de Discord para forzar verificación inmediata
     pub fn invalidate_discord_cache(&mut self) {
         self.discord_running_cache = None;
-        self.discord_versions_cache = None;
+        self.discord_installs_cache = None;
         self.last_discord_check = None;
         log::debug!("Discord cache invalidated");
     }
@@ -105,30 +313,165 @@ impl DsqApp {
             return;
         }
 
-        let dead_processes = self.process_monitor.check_and_remove_dead_processes();
+        let finished_sessions = self.process_monitor.check_and_remove_dead_processes();
 
         // Si hay procesos que murieron y Rich Presence está activo, resetear
-        if !dead_processes.is_empty() {
+        if !finished_sessions.is_empty() {
+            for session in &finished_sessions {
+                log::info!(
+                    "Session {} ended after {}s: {:?}",
+                    session.preset_name,
+                    session.duration().as_secs(),
+                    session.executables
+                );
+            }
+
+            self.record_finished_sessions(&finished_sessions);
+            for session in &finished_sessions {
+                self.queue.finish_group(session.group_id);
+            }
+
+            // Si sigue otra sesión activa, Rich Presence pasa a mostrarla
+            let activity = self
+                .process_monitor
+                .active_sessions()
+                .last()
+                .map(|session| session.preset_name.clone());
+
+            log::info!("Rich Presence reset to {:?}", activity);
+            self.current_simulated_game = activity;
+            self.update_rich_presence();
+        }
+    }
+
+    /// Avanza la cola de simulaciones: detiene el elemento que superó su
+    /// tiempo e inicia el siguiente cuando el anterior terminó
+    pub fn process_queue(&mut self) {
+        if let Some(group_id) = self.queue.overdue_group() {
+            log::info!("Queue item in group {} is overdue, stopping it", group_id);
+            self.process_monitor
+                .stop_group(group_id, ExitReason::ScheduledClose);
+        }
+
+        let Some(item) = self.queue.next_ready() else {
+            return;
+        };
+
+        let preset_index = self
+            .presets
+            .iter()
+            .position(|preset| preset.name.eq_ignore_ascii_case(&item.preset));
+
+        let Some(preset_index) = preset_index else {
+            log::warn!("Skipping queue item, preset not found: {}", item.preset);
+            self.status = translate(self, "preset_not_found").replace("{name}", &item.preset);
+            return;
+        };
+
+        match start_preset(self, preset_index, item.duration_minutes) {
+            Some(group_id) => self.queue.start(item, group_id),
+            None => {
+                // No reintentar en bucle: pausar la cola y devolver el elemento
+                log::error!("Failed to start queue item {}, pausing queue", item.preset);
+                self.queue.pending.insert(0, item);
+                self.queue.running = false;
+            }
+        }
+    }
+
+    /// Encola las simulaciones programadas que llegaron a su hora
+    pub fn process_schedules(&mut self) {
+        const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+        if self
+            .last_schedule_check
+            .is_some_and(|last_check| last_check.elapsed() < CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_schedule_check = Some(Instant::now());
+
+        if self.schedules.is_empty() {
+            return;
+        }
+
+        // El lock compartido se mantiene hasta guardar, así `scheduler run`
+        // no puede arrancar en medio de esta revisión
+        let lock = SchedulerLock::acquire_shared();
+        if let Err(e) = &lock {
+            log::warn!("Failed to check the scheduler lock: {}", e);
+        }
+        let headless = matches!(lock, Ok(None));
+        let released = self.headless_scheduler && !headless;
+        if headless != self.headless_scheduler {
             log::info!(
-                "Detected {} dead process(es): {:?}",
-                dead_processes.len(),
-                dead_processes
+                "Headless scheduler {}",
+                if headless { "started, pausing schedules" } else { "stopped, resuming schedules" }
             );
+            self.headless_scheduler = headless;
+        }
 
-            if self.rich_presence_enabled {
-                if let Some(ref mut rp) = self.rich_presence {
-                    match rp.set_activity(None) {
-                        Ok(_) => {
-                            log::info!("Rich Presence reset to idle state");
-                            self.current_simulated_game = None;
-                        }
-                        Err(e) => {
-                            log::error!("Failed to reset Rich Presence: {}", e);
-                        }
-                    }
-                }
+        // Tomar las ejecuciones que registró `scheduler run` para no repetirlas
+        if (headless || released) && self.config_load_error.is_none() {
+            if let Ok(config) = load_config() {
+                merge_last_runs(&mut self.schedules, &config.schedules);
             }
         }
+        if headless {
+            return;
+        }
+
+        let now = Local::now();
+        if self.schedules.iter().all(|schedule| schedule.check(now).is_none()) {
+            return;
+        }
+
+        let due = take_due_runs(&mut self.schedules, now);
+        self.schedules.retain(|schedule| !schedule.is_finished());
+        save_settings_config(self);
+
+        for (preset, duration_minutes) in due {
+            self.status = translate(self, "schedule_started").replace("{name}", &preset);
+            self.queue.push(&preset, duration_minutes);
+            self.queue.running = true;
+        }
+    }
+
+    /// Salta al siguiente elemento de la cola
+    pub fn skip_queue_item(&mut self) {
+        if let Some(group_id) = self.queue.skip() {
+            self.process_monitor.stop_group(group_id, ExitReason::Manual);
+        }
+    }
+
+    /// Guarda las sesiones terminadas en el historial y suma su tiempo
+    /// al progreso de las misiones
+    fn record_finished_sessions(&mut self, sessions: &[FinishedSession]) {
+        let records: Vec<SessionRecord> = sessions.iter().map(SessionRecord::from).collect();
+        if let Err(e) = append_history(&records) {
+            log::error!("Failed to save session history: {}", e);
+        }
+        self.session_history.extend(records);
+
+        let mut changed = false;
+        let mut completed = Vec::new();
+        for session in sessions {
+            let progress = record_session(&mut self.quests, &session.preset_name, session.duration());
+            changed |= progress.changed;
+            completed.extend(progress.completed);
+        }
+
+        if changed {
+            if self.quests_load_error.is_some() {
+                log::warn!("Not saving quest progress because quests.json failed to load");
+            } else if let Err(e) = save_quests(&self.quests) {
+                log::error!("Failed to save quests: {}", e);
+            }
+        }
+
+        if !completed.is_empty() {
+            self.status = translate(self, "quest_completed").replace("{name}", &completed.join(", "));
+        }
     }
 
     /// Limpia todos los recursos antes de cerrar
@@ -138,12 +481,14 @@ impl DsqApp {
         // Limpiar Rich Presence
         if let Some(mut rp) = self.rich_presence.take() {
             log::info!("Cleaning up Rich Presence");
-            let _ = rp.clear_activity();
-            rp.disconnect();
+            rp.shutdown();
         }
 
-        // Limpiar procesos monitoreados
-        self.process_monitor.cleanup_all();
+        // Limpiar procesos monitoreados y registrar las sesiones activas
+        let sessions = self.process_monitor.cleanup_all();
+        if !sessions.is_empty() {
+            self.record_finished_sessions(&sessions);
+        }
 
         log::info!("App cleanup completed");
     }
@@ -152,7 +497,26 @@ impl DsqApp {
 impl App for DsqApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Verificar procesos muertos periódicamente
+        self.poll_discord();
+        self.poll_discord_launch(ctx);
         self.check_dead_processes();
+        self.process_schedules();
+        self.process_queue();
+        handle_api_requests(self);
+        self.sync_rich_presence(ctx);
+        self.refresh_rich_presence();
+
+        // Mientras la cola está activa hay que seguir revisando aunque no haya eventos
+        if self.queue.running || self.queue.current.is_some() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        } else if self.presence_needs_refresh() {
+            ctx.request_repaint_after(PRESENCE_REFRESH_INTERVAL);
+        } else if !self.schedules.is_empty()
+            || self.auto_start.enabled
+            || self.auto_start.stop_on_discord_exit
+        {
+            ctx.request_repaint_after(Duration::from_secs(5));
+        }
 
         // Renderizar UI
         render_ui(self, ctx);
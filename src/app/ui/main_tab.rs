@@ -1,29 +1,32 @@
 use super::components;
+use super::schedule_panel;
+use super::settings_tab::save_settings_config;
+use super::preset_dialog::{format_env, join_args};
 use crate::app::state::DsqApp;
 use crate::app::translate::translate;
-use crate::core::presets::{is_presets_outdated, load_presets, update_presets_file};
-use crate::core::process::create_fake_process;
-use crate::platform::discord::{
-    get_installed_discord_versions, is_discord_running, open_discord, DiscordVersion,
-};
+use crate::core::presets::{is_presets_outdated, update_presets_file};
+use crate::core::process::{create_fake_process_group, LaunchOptions};
+use crate::shared::types::{Preset, PresetExecutable};
+use crate::app::launch::DISCORD_READY_TIMEOUT;
+use crate::platform::discord::InstallKind;
+use crate::shared::richpresence::PresenceStatus;
 use eframe::egui;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub fn render(ui: &mut egui::Ui, app: &mut DsqApp) {
-    // Solo actualizar cache de Discord cuando sea necesario
-    if app.should_check_discord() {
-        app.discord_running_cache = Some(is_discord_running());
-        if app.discord_running_cache == Some(false) {
-            app.discord_versions_cache = Some(get_installed_discord_versions());
-        }
-    }
-
-    ui.vertical_centered(|ui| {
-        render_rich_presence_status(ui, app);
-        render_discord_detection(ui, app);
-        render_presets_section(ui, app);
-        render_outdated_presets_warning(ui, app);
-        render_process_configuration(ui, app);
-        render_status_section(ui, app);
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        ui.vertical_centered(|ui| {
+            render_rich_presence_status(ui, app);
+            render_discord_detection(ui, app);
+            render_automation_status(ui, app);
+            render_preset_errors(ui, app);
+            render_presets_section(ui, app);
+            render_outdated_presets_warning(ui, app);
+            render_process_configuration(ui, app);
+            render_queue_section(ui, app);
+            schedule_panel::render(ui, app);
+            render_status_section(ui, app);
+        });
     });
 }
 
@@ -32,23 +35,25 @@ fn render_rich_presence_status(ui: &mut egui::Ui, app: &mut DsqApp) {
         return;
     }
 
-    let (status_text, color) = if let Some(ref rp) = app.rich_presence {
-        if rp.is_connected() {
-            (
-                translate(app, "rich_presence_connected"),
-                egui::Color32::GREEN,
-            )
-        } else {
-            (
-                translate(app, "rich_presence_disconnected"),
-                egui::Color32::RED,
-            )
-        }
-    } else {
-        (
+    let status = app
+        .rich_presence
+        .as_ref()
+        .map(|rp| rp.status())
+        .unwrap_or(PresenceStatus::Connecting);
+
+    let (status_text, color) = match status {
+        PresenceStatus::Connected => (
+            translate(app, "rich_presence_connected"),
+            egui::Color32::GREEN,
+        ),
+        PresenceStatus::Connecting => (
+            translate(app, "rich_presence_connecting"),
+            egui::Color32::from_rgb(255, 193, 7),
+        ),
+        PresenceStatus::Disconnected => (
             translate(app, "rich_presence_disconnected"),
             egui::Color32::RED,
-        )
+        ),
     };
 
     ui.colored_label(color, status_text);
@@ -56,43 +61,46 @@ fn render_rich_presence_status(ui: &mut egui::Ui, app: &mut DsqApp) {
 }
 
 fn render_discord_detection(ui: &mut egui::Ui, app: &mut DsqApp) {
+    if app.discord_launch.is_some() {
+        render_discord_launch(ui, app);
+        return;
+    }
+
     // Usar cache para evitar verificaciones constantes
     let discord_is_running = app.discord_running_cache.unwrap_or(true);
 
+    if discord_is_running && app.discord_running_cache.is_some() {
+        render_ipc_sockets(ui, app);
+    }
+
     if !discord_is_running {
         ui.colored_label(egui::Color32::RED, translate(app, "discord_not_running"));
 
-        let installed_versions = app
-            .discord_versions_cache
+        let installs = app
+            .discord_installs_cache
             .as_ref()
             .cloned()
             .unwrap_or_default();
 
-        if !installed_versions.is_empty() {
+        if !installs.is_empty() {
             ui.add_space(5.0);
             ui.label(translate(app, "start_discord_prompt"));
             ui.vertical_centered(|ui| {
-                for version in installed_versions {
-                    let label = match version {
-                        DiscordVersion::Stable => "🍱 Discord",
-                        DiscordVersion::PTB => "🔎 Discord PTB",
-                        DiscordVersion::Canary => "🛠 Discord Canary",
-                    };
+                for install in installs {
+                    let mut label = format!("{} {}", install.client.icon, install.client.name);
+                    if !matches!(install.kind, InstallKind::Squirrel | InstallKind::Custom) {
+                        label = format!("{} ({})", label, translate(app, install.kind.lang_key()));
+                    }
 
                     if ui.button(label).clicked() {
-                        match open_discord(version) {
-                            Ok(_) => {
-                                log::info!("Discord opened successfully");
-                                // Invalidar cache para verificar inmediatamente
-                                app.invalidate_discord_cache();
-                            }
-                            Err(e) => {
-                                log::error!("Failed to open Discord: {}", e);
-                                app.status = format!("Error al abrir Discord: {}", e);
-                            }
-                        }
+                        app.launch_discord(&install);
                     }
                 }
+
+                let start_text = translate(app, "launch_start_preset");
+                if ui.checkbox(&mut app.launch_start_preset, start_text).changed() {
+                    save_settings_config(app);
+                }
             });
         } else {
             ui.label(translate(app, "discord_not_installed"));
@@ -103,6 +111,105 @@ fn render_discord_detection(ui: &mut egui::Ui, app: &mut DsqApp) {
     }
 }
 
+/// Progreso mientras se espera a que Discord abra su socket IPC
+fn render_discord_launch(ui: &mut egui::Ui, app: &mut DsqApp) {
+    let Some(launch) = &app.discord_launch else {
+        return;
+    };
+
+    let text = translate(app, "discord_waiting")
+        .replace("{name}", &launch.client_name)
+        .replace("{elapsed}", &launch.elapsed().as_secs().to_string())
+        .replace("{seconds}", &DISCORD_READY_TIMEOUT.as_secs().to_string());
+    let progress = launch.elapsed().as_secs_f32() / DISCORD_READY_TIMEOUT.as_secs_f32();
+
+    ui.horizontal(|ui| {
+        ui.spinner();
+        ui.colored_label(egui::Color32::from_rgb(255, 193, 7), text);
+    });
+    ui.add(egui::ProgressBar::new(progress.min(1.0)).desired_width(300.0));
+    if ui
+        .button("✖ ".to_string() + &translate(app, "discord_launch_cancel"))
+        .clicked()
+    {
+        app.cancel_discord_launch();
+    }
+    ui.add_space(10.0);
+    ui.separator();
+    ui.add_space(10.0);
+}
+
+/// Qué cliente atiende cada socket IPC y cuál usa Rich Presence
+fn render_ipc_sockets(ui: &mut egui::Ui, app: &DsqApp) {
+    let detection = &app.discord_detection;
+    if detection.sockets.is_empty() {
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 193, 7),
+            translate(app, "discord_no_ipc"),
+        );
+        ui.add_space(10.0);
+        return;
+    }
+
+    let unknown = translate(app, "discord_ipc_unknown");
+    let used_by_presence = translate(app, "discord_ipc_rich_presence");
+    for socket in &detection.sockets {
+        let client = socket
+            .owner
+            .as_ref()
+            .map_or(unknown.clone(), |client| client.name.clone());
+        let mut text = translate(app, "discord_ipc_socket")
+            .replace("{socket}", &socket.name())
+            .replace("{client}", &client);
+        if app.rich_presence_enabled && detection.rich_presence_socket() == Some(socket) {
+            text = format!("{} {}", text, used_by_presence);
+        }
+        ui.colored_label(egui::Color32::GRAY, text)
+            .on_hover_text(socket.path.display().to_string());
+    }
+    ui.add_space(10.0);
+}
+
+/// Muestra la regla de automatización activa
+fn render_automation_status(ui: &mut egui::Ui, app: &mut DsqApp) {
+    let rule = &app.auto_start;
+    if !rule.enabled && !rule.stop_on_discord_exit {
+        return;
+    }
+
+    let color = egui::Color32::from_rgb(108, 117, 125);
+    if rule.enabled {
+        let preset = rule
+            .preset
+            .clone()
+            .or_else(|| app.last_used_preset.clone())
+            .unwrap_or_else(|| "-".to_string());
+        ui.colored_label(color, translate(app, "auto_start_active").replace("{name}", &preset));
+    }
+    if rule.stop_on_discord_exit {
+        ui.colored_label(color, translate(app, "auto_stop_active"));
+    }
+    ui.add_space(10.0);
+}
+
+fn render_preset_errors(ui: &mut egui::Ui, app: &mut DsqApp) {
+    if app.preset_errors.is_empty() {
+        return;
+    }
+
+    let title = translate(app, "preset_parse_errors")
+        .replace("{count}", &app.preset_errors.len().to_string());
+
+    egui::CollapsingHeader::new(egui::RichText::new(title).color(egui::Color32::YELLOW))
+        .id_source("preset_parse_errors")
+        .show(ui, |ui| {
+            for error in &app.preset_errors {
+                ui.colored_label(egui::Color32::from_rgb(108, 117, 125), error.to_string());
+            }
+        });
+    ui.add_space(10.0);
+}
+
 fn render_presets_section(ui: &mut egui::Ui, app: &mut DsqApp) {
     if app.presets.is_empty() {
         ui.colored_label(egui::Color32::YELLOW, "⚠ No hay presets disponibles");
@@ -121,20 +228,53 @@ fn render_presets_section(ui: &mut egui::Ui, app: &mut DsqApp) {
                 log::warn!("Selected preset out of bounds, reset to 0");
             }
 
+            render_tag_filter(ui, app);
+
+            let unsupported_text = translate(app, "preset_no_host_variant");
+            let no_matches_text = translate(app, "no_presets_match");
+            let uncategorized = translate(app, "uncategorized");
+
+            // Agrupar por categoría los presets que pasan el filtro de etiquetas.
+            // Los presets sin categoría van al final.
+            let mut groups: BTreeMap<(bool, String), Vec<usize>> = BTreeMap::new();
+            for (i, preset) in app.presets.iter().enumerate() {
+                if preset.has_tags(&app.preset_tag_filter) {
+                    let key = match &preset.category {
+                        Some(category) => (false, category.clone()),
+                        None => (true, uncategorized.clone()),
+                    };
+                    groups.entry(key).or_default().push(i);
+                }
+            }
+            let grouped = app.presets.iter().any(|preset| preset.category.is_some());
+
             egui::ComboBox::from_label("")
-                .selected_text(&app.presets[app.selected_preset].name)
+                .selected_text(preset_label(&app.presets[app.selected_preset]))
                 .width(300.0)
                 .show_ui(ui, |ui| {
-                    for (i, preset) in app.presets.iter().enumerate() {
-                        let label = if preset.is_custom {
-                            format!("⭐ {}", preset.name)
-                        } else {
-                            preset.name.clone()
-                        };
-                        ui.selectable_value(&mut app.selected_preset, i, label);
+                    if groups.is_empty() {
+                        ui.label(&no_matches_text);
+                    }
+
+                    for ((_, category), indices) in &groups {
+                        if !grouped {
+                            render_preset_entries(ui, app, indices, &unsupported_text);
+                            continue;
+                        }
+
+                        egui::CollapsingHeader::new(format!("{} ({})", category, indices.len()))
+                            .id_source(("preset_group", category))
+                            .default_open(indices.contains(&app.selected_preset))
+                            .show(ui, |ui| {
+                                render_preset_entries(ui, app, indices, &unsupported_text);
+                            });
                     }
                 });
 
+            if !app.presets[app.selected_preset].supports_host() {
+                ui.colored_label(egui::Color32::YELLOW, &unsupported_text);
+            }
+
             ui.add_space(5.0);
 
             // Primera fila de botones
@@ -146,14 +286,22 @@ fn render_presets_section(ui: &mut egui::Ui, app: &mut DsqApp) {
                     app.show_add_preset_dialog = true;
                 }
 
+                if ui
+                    .button("📥 ".to_string() + &translate(app, "import_presets"))
+                    .clicked()
+                {
+                    app.show_import_dialog = true;
+                }
+
                 if ui
                     .button("📋 ".to_string() + &translate(app, "use_preset"))
                     .clicked()
                 {
-                    let preset = &app.presets[app.selected_preset];
-                    app.process_name = preset.executable.clone();
-                    app.custom_path = preset.path.clone();
-                    log::info!("Using preset: {}", preset.name);
+                    let preset = app.presets[app.selected_preset].clone();
+                    let minutes = preset
+                        .duration_minutes
+                        .unwrap_or(app.default_duration_minutes);
+                    use_preset(app, &preset, minutes);
                 }
 
                 if ui
@@ -181,8 +329,16 @@ fn render_presets_section(ui: &mut egui::Ui, app: &mut DsqApp) {
                         let preset = &app.presets[app.selected_preset];
                         app.preset_to_edit = Some(preset.name.clone());
                         app.new_preset_name = preset.name.clone();
-                        app.new_preset_executable = preset.executable.clone();
-                        app.new_preset_path = preset.path.clone();
+                        app.new_preset_executables = preset.executables.clone();
+                        app.new_preset_category = preset.category.clone().unwrap_or_default();
+                        app.new_preset_tags = preset.tags.join(", ");
+                        app.new_preset_args = join_args(&preset.args);
+                        app.new_preset_cwd = preset.cwd.clone().unwrap_or_default();
+                        app.new_preset_env = format_env(&preset.env);
+                        app.new_preset_duration = preset
+                            .duration_minutes
+                            .map(|minutes| minutes.to_string())
+                            .unwrap_or_default();
                         app.show_edit_preset_dialog = true;
                     }
 
@@ -201,6 +357,79 @@ fn render_presets_section(ui: &mut egui::Ui, app: &mut DsqApp) {
     ui.add_space(10.0);
 }
 
+fn render_tag_filter(ui: &mut egui::Ui, app: &mut DsqApp) {
+    // En minúsculas para no repetir "RPG" y "rpg" como chips distintos
+    let tags: BTreeSet<String> = app
+        .presets
+        .iter()
+        .flat_map(|preset| preset.tags.iter().map(|tag| tag.trim().to_lowercase()))
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
+    // Descartar filtros de etiquetas que ya no existen
+    app.preset_tag_filter.retain(|tag| tags.contains(tag));
+
+    if tags.is_empty() {
+        return;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label("🏷");
+        for tag in tags {
+            let active = app.preset_tag_filter.contains(&tag);
+            if ui.selectable_label(active, &tag).clicked() {
+                if active {
+                    app.preset_tag_filter.retain(|t| *t != tag);
+                } else {
+                    app.preset_tag_filter.push(tag);
+                }
+            }
+        }
+
+        if !app.preset_tag_filter.is_empty()
+            && ui
+                .small_button("✖")
+                .on_hover_text(translate(app, "clear_tag_filter"))
+                .clicked()
+        {
+            app.preset_tag_filter.clear();
+        }
+    });
+    ui.add_space(5.0);
+}
+
+fn render_preset_entries(
+    ui: &mut egui::Ui,
+    app: &mut DsqApp,
+    indices: &[usize],
+    unsupported_text: &str,
+) {
+    for &i in indices {
+        let preset = &app.presets[i];
+        let label = preset_label(preset);
+        let supported = preset.supports_host();
+        let mut response = ui.selectable_value(&mut app.selected_preset, i, label);
+        if !supported {
+            response = response.on_hover_text(unsupported_text);
+        }
+        if !app.presets[i].tags.is_empty() {
+            response.on_hover_text(app.presets[i].tags.join(", "));
+        }
+    }
+}
+
+/// Etiqueta del preset en el selector: ⭐ personalizados, ⚠ sin variante para este sistema
+fn preset_label(preset: &Preset) -> String {
+    let mut label = preset.name.clone();
+    if preset.is_custom {
+        label = format!("⭐ {}", label);
+    }
+    if !preset.supports_host() {
+        label = format!("⚠ {}", label);
+    }
+    label
+}
+
 fn render_outdated_presets_warning(ui: &mut egui::Ui, app: &mut DsqApp) {
     if !app.presets_outdated {
         return;
@@ -219,15 +448,9 @@ fn render_outdated_presets_warning(ui: &mut egui::Ui, app: &mut DsqApp) {
                 match update_presets_file() {
                     Ok(_) => {
                         app.status = translate(app, "presets_updated");
-                        app.presets = load_presets();
-                        app.filtered_presets = app.presets.clone();
+                        app.reload_presets();
                         app.presets_outdated = false;
 
-                        // Validar selected_preset después de actualizar
-                        if app.selected_preset >= app.presets.len() {
-                            app.selected_preset = 0;
-                        }
-
                         log::info!("Presets updated successfully");
                     }
                     Err(e) => {
@@ -260,6 +483,17 @@ fn render_process_configuration(ui: &mut egui::Ui, app: &mut DsqApp) {
                 ui.text_edit_singleline(&mut app.custom_path);
             });
 
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "duration_label"));
+                ui.add(
+                    egui::DragValue::new(&mut app.duration_minutes)
+                        .clamp_range(1..=1440)
+                        .suffix(" min"),
+                );
+            });
+
             // Mostrar preview de la ruta completa
             if !app.custom_path.is_empty() {
                 let full_path = if app.custom_path.starts_with("Games/")
@@ -281,24 +515,83 @@ fn render_process_configuration(ui: &mut egui::Ui, app: &mut DsqApp) {
                 );
             }
 
+            // Ejecutables adicionales que se inician junto al principal
+            let extra_executables: Vec<String> = app
+                .configured_preset()
+                .map(|preset| {
+                    // El principal no siempre es el primero de la lista
+                    let primary = preset.primary();
+                    preset
+                        .executables
+                        .iter()
+                        .filter(|exe| !primary.is_some_and(|p| std::ptr::eq(p, *exe)))
+                        .map(|exe| exe.for_host().executable)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if !extra_executables.is_empty() {
+                ui.add_space(3.0);
+                ui.colored_label(
+                    egui::Color32::from_rgb(108, 117, 125),
+                    translate(app, "group_extra_executables")
+                        .replace("{list}", &extra_executables.join(", ")),
+                );
+            }
+
             ui.add_space(10.0);
 
             if ui
                 .button("🚀 ".to_string() + &translate(app, "start_process"))
                 .clicked()
             {
-                handle_start_process(app);
+                let preset = app.configured_preset().cloned();
+                handle_start_process(app, preset.as_ref());
             }
         });
     });
 }
 
-fn handle_start_process(app: &mut DsqApp) {
-    let process_name = app.process_name.trim();
+/// Carga un preset en la configuración del proceso
+fn use_preset(app: &mut DsqApp, preset: &Preset, duration_minutes: u64) {
+    if let Some(exe) = preset.primary().map(|exe| exe.for_host()) {
+        app.process_name = exe.executable.clone();
+        app.custom_path = exe.path.clone();
+    }
+    app.duration_minutes = duration_minutes;
+    log::info!("Using preset: {}", preset.name);
+}
+
+/// Inicia un preset con una duración concreta.
+/// Devuelve el grupo de procesos iniciado.
+pub fn start_preset(app: &mut DsqApp, preset_index: usize, duration_minutes: u64) -> Option<u64> {
+    let preset = app.presets[preset_index].clone();
+    app.selected_preset = preset_index;
+    use_preset(app, &preset, duration_minutes);
+    handle_start_process(app, Some(&preset))
+}
+
+/// Inicia un ejecutable y ruta concretos, sin pasar por la lista de presets.
+/// Devuelve el grupo de procesos iniciado.
+pub fn start_executable(
+    app: &mut DsqApp,
+    executable: &str,
+    path: &str,
+    duration_minutes: u64,
+) -> Option<u64> {
+    app.process_name = executable.to_string();
+    app.custom_path = path.to_string();
+    app.duration_minutes = duration_minutes;
+    let preset = app.configured_preset().cloned();
+    handle_start_process(app, preset.as_ref())
+}
+
+/// Inicia el ejecutable configurado. Con `preset`, inicia todos sus ejecutables.
+fn handle_start_process(app: &mut DsqApp, preset: Option<&Preset>) -> Option<u64> {
+    let process_name = app.process_name.trim().to_string();
 
     if process_name.is_empty() {
         app.status = translate(app, "error_empty");
-        return;
+        return None;
     }
 
     // Calcular ruta completa para el mensaje
@@ -316,51 +609,182 @@ fn handle_start_process(app: &mut DsqApp) {
 
     log::info!("Starting fake process: {} at {}", process_name, full_path);
 
-    match create_fake_process(&app.custom_path, process_name, 15) {
-        Ok((pid, exe_path)) => {
-            log::info!("Fake process created successfully: PID {}", pid);
+    let preset_name = preset.map(|preset| preset.name.clone());
+    let (executables, launch) = match preset {
+        Some(preset) => (preset.host_executables(), LaunchOptions::from_preset(preset)),
+        None => (
+            vec![PresetExecutable {
+                executable: process_name.to_string(),
+                path: app.custom_path.clone(),
+                ..Default::default()
+            }],
+            LaunchOptions {
+                preset_name: process_name.trim_end_matches(".exe").to_string(),
+                ..Default::default()
+            },
+        ),
+    };
+
+    match create_fake_process_group(&executables, &launch, app.duration_minutes) {
+        Ok(spawned) => {
+            log::info!("Fake process group created successfully: {:?}", spawned);
 
-            // Registrar proceso en el monitor
-            app.process_monitor
-                .add_process(pid, process_name.to_string(), exe_path);
+            // Registrar procesos en el monitor
+            let group_id = app
+                .process_monitor
+                .add_process_group(&launch.preset_name, app.duration_minutes, spawned);
 
-            // Actualizar Rich Presence si está habilitado
-            if app.rich_presence_enabled {
-                if let Some(ref mut rp) = app.rich_presence {
-                    // Buscar nombre del juego en presets
-                    let game_display_name = app
-                        .presets
+            // Recordar el preset para la automatización al abrirse Discord
+            if preset_name.is_some() && app.last_used_preset != preset_name {
+                app.last_used_preset = preset_name.clone();
+                save_settings_config(app);
+            }
+
+            // Sin preset, buscar el nombre del juego por ejecutable
+            let game_display_name = preset_name
+                .or_else(|| {
+                    app.presets
                         .iter()
-                        .find(|preset| preset.executable == process_name)
+                        .find(|preset| {
+                            preset
+                                .host_executables()
+                                .iter()
+                                .any(|exe| exe.executable == process_name)
+                        })
                         .map(|preset| preset.name.clone())
-                        .unwrap_or_else(|| process_name.replace(".exe", ""));
+                })
+                .unwrap_or_else(|| process_name.replace(".exe", ""));
+            app.current_simulated_game = Some(game_display_name);
 
-                    match rp.set_activity(Some(game_display_name.clone())) {
-                        Ok(_) => {
-                            app.current_simulated_game = Some(game_display_name);
-                            log::info!("Rich Presence updated successfully");
-                        }
-                        Err(e) => {
-                            log::error!("Failed to update Rich Presence: {}", e);
-                            app.status = translate(app, "rich_presence_error")
-                                .replace("{error}", &e.to_string());
-                            return;
-                        }
-                    }
-                }
-            }
+            // Actualizar Rich Presence si está habilitado
+            app.update_rich_presence();
 
             app.status = translate(app, "success")
-                .replace("{name}", process_name)
+                .replace("{name}", &process_name)
                 .replace("{path}", &full_path);
+            Some(group_id)
         }
         Err(e) => {
             log::error!("Failed to create fake process: {}", e);
             app.status = translate(app, "error").replace("{error}", &e.to_string());
+            None
         }
     }
 }
 
+/// Acción sobre un elemento pendiente de la cola
+enum QueueAction {
+    MoveUp(usize),
+    MoveDown(usize),
+    Remove(usize),
+}
+
+fn render_queue_section(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            let count = app.queue.pending.len() + usize::from(app.queue.current.is_some());
+            egui::CollapsingHeader::new(format!("{} ({})", translate(app, "queue"), count))
+                .id_source("simulation_queue")
+                .default_open(!app.queue.is_empty())
+                .show(ui, |ui| {
+                    if let Some(current) = &app.queue.current {
+                        let remaining = current.remaining().as_secs();
+                        ui.label(format!("▶ {}", current.item.preset));
+                        ui.add(
+                            egui::ProgressBar::new(current.progress())
+                                .desired_width(300.0)
+                                .text(format!("{:02}:{:02}", remaining / 60, remaining % 60)),
+                        );
+                        ui.add_space(5.0);
+                    }
+
+                    let mut action = None;
+                    let last = app.queue.pending.len().saturating_sub(1);
+                    for (index, item) in app.queue.pending.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{}. {}", index + 1, item.preset));
+                            ui.add(
+                                egui::DragValue::new(&mut item.duration_minutes)
+                                    .clamp_range(1..=1440)
+                                    .suffix(" min"),
+                            );
+                            if ui.add_enabled(index > 0, egui::Button::new("⬆")).clicked() {
+                                action = Some(QueueAction::MoveUp(index));
+                            }
+                            if ui.add_enabled(index < last, egui::Button::new("⬇")).clicked() {
+                                action = Some(QueueAction::MoveDown(index));
+                            }
+                            if ui.button("🗑").clicked() {
+                                action = Some(QueueAction::Remove(index));
+                            }
+                        });
+                    }
+
+                    match action {
+                        Some(QueueAction::MoveUp(index)) => app.queue.move_up(index),
+                        Some(QueueAction::MoveDown(index)) => app.queue.move_down(index),
+                        Some(QueueAction::Remove(index)) => app.queue.remove(index),
+                        None => {}
+                    }
+
+                    if app.queue.is_empty() {
+                        ui.colored_label(egui::Color32::GRAY, translate(app, "queue_empty"));
+                    }
+
+                    ui.add_space(5.0);
+                    render_queue_controls(ui, app);
+                });
+        });
+    });
+}
+
+fn render_queue_controls(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.horizontal(|ui| {
+        if ui
+            .button("➕ ".to_string() + &translate(app, "queue_add"))
+            .clicked()
+        {
+            if let Some(preset) = app.presets.get(app.selected_preset) {
+                let minutes = preset
+                    .duration_minutes
+                    .unwrap_or(app.default_duration_minutes);
+                app.queue.push(&preset.name, minutes);
+            }
+        }
+
+        if app.queue.running {
+            if ui
+                .button("⏸ ".to_string() + &translate(app, "queue_pause"))
+                .clicked()
+            {
+                log::info!("Simulation queue paused");
+                app.queue.running = false;
+            }
+        } else if ui
+            .add_enabled(
+                !app.queue.pending.is_empty(),
+                egui::Button::new("▶ ".to_string() + &translate(app, "queue_start")),
+            )
+            .clicked()
+        {
+            log::info!("Simulation queue started");
+            app.queue.running = true;
+        }
+
+        if ui
+            .add_enabled(
+                app.queue.current.is_some(),
+                egui::Button::new("⏭ ".to_string() + &translate(app, "queue_skip")),
+            )
+            .clicked()
+        {
+            app.skip_queue_item();
+        }
+    });
+}
+
 fn render_status_section(ui: &mut egui::Ui, app: &mut DsqApp) {
     if app.status.is_empty() {
         return;
@@ -1,8 +1,12 @@
 pub mod main_tab;
+pub mod quests_tab;
+pub mod schedule_panel;
 pub mod settings_tab;
+pub mod stats_tab;
 pub mod about_tab;
 pub mod components;
 pub mod preset_dialog;
+pub mod presence_panel;
 
 use eframe::egui;
 use crate::app::state::{ DsqApp, Tab };
@@ -26,6 +30,8 @@ pub fn render_ui(app: &mut DsqApp, ctx: &egui::Context) {
 
                 match app.selected_tab {
                     Tab::Main => main_tab::render(ui, app),
+                    Tab::Quests => quests_tab::render(ui, app),
+                    Tab::Stats => stats_tab::render(ui, app),
                     Tab::Settings => settings_tab::render(ui, app),
                     Tab::About => about_tab::render(ui, app),
                 }
@@ -34,16 +40,33 @@ pub fn render_ui(app: &mut DsqApp, ctx: &egui::Context) {
     preset_dialog::render_add_dialog(ctx, app);
     preset_dialog::render_edit_dialog(ctx, app);
     preset_dialog::render_delete_confirmation(ctx, app);
+    preset_dialog::render_import_dialog(ctx, app);
 }
 
 fn render_tab_navigation(ui: &mut egui::Ui, app: &mut DsqApp) {
     ui.horizontal(|ui| {
-        ui.add_space((ui.available_width() - 465.0) / 2.0);
+        ui.add_space(((ui.available_width() - 465.0) / 2.0).max(0.0));
 
         if ui.selectable_label(app.selected_tab == Tab::Main, translate(app, "tab_main")).clicked() {
             app.selected_tab = Tab::Main;
         }
 
+        if
+            ui
+                .selectable_label(app.selected_tab == Tab::Quests, translate(app, "tab_quests"))
+                .clicked()
+        {
+            app.selected_tab = Tab::Quests;
+        }
+
+        if
+            ui
+                .selectable_label(app.selected_tab == Tab::Stats, translate(app, "tab_stats"))
+                .clicked()
+        {
+            app.selected_tab = Tab::Stats;
+        }
+
         if
             ui
                 .selectable_label(app.selected_tab == Tab::Settings, translate(app, "tab_settings"))
@@ -0,0 +1,288 @@
+use crate::app::state::DsqApp;
+use crate::app::translate::translate;
+use crate::shared::richpresence::template::{
+    is_valid_asset_key, is_valid_client_id, ActivityButton, ActivityTemplate,
+    MultiSessionDisplay, ACTIVITY_PLACEHOLDERS, DEFAULT_CLIENT_ID, DEFAULT_LARGE_IMAGE,
+};
+use eframe::egui;
+
+/// Editor de la plantilla de actividad de Rich Presence
+pub fn render(ui: &mut egui::Ui, app: &mut DsqApp) {
+    render_application(ui, app);
+    render_activity(ui, app);
+}
+
+/// Aplicación de Discord e imágenes de cada preset
+fn render_application(ui: &mut egui::Ui, app: &mut DsqApp) {
+    egui::CollapsingHeader::new(translate(app, "rp_application"))
+        .id_source("rich_presence_application")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "rp_client_id"));
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut app.client_id_input)
+                        .desired_width(170.0)
+                        .hint_text(DEFAULT_CLIENT_ID),
+                );
+
+                // Se aplica al salir del campo para no reconectar con cada tecla
+                let input = app.client_id_input.trim().to_string();
+                if response.lost_focus()
+                    && (input.is_empty() || is_valid_client_id(&input))
+                    && input != app.rich_presence_client_id
+                {
+                    log::info!("Rich Presence application ID changed");
+                    app.rich_presence_client_id = input;
+                }
+            });
+
+            let input = app.client_id_input.trim();
+            if !input.is_empty() && !is_valid_client_id(input) {
+                ui.colored_label(egui::Color32::RED, translate(app, "rp_invalid_client_id"));
+            }
+
+            ui.add_space(5.0);
+            if render_preset_images(ui, app) {
+                app.update_rich_presence();
+            }
+        });
+}
+
+fn render_preset_images(ui: &mut egui::Ui, app: &mut DsqApp) -> bool {
+    let mut changed = false;
+    let mut to_remove = None;
+
+    ui.label(translate(app, "rp_preset_images"));
+    let invalid_text = translate(app, "rp_invalid_asset_key");
+    for (preset, key) in app.rich_presence_template.preset_images.iter_mut() {
+        ui.horizontal(|ui| {
+            ui.label(preset.as_str());
+            changed |= ui
+                .add(egui::TextEdit::singleline(key).desired_width(150.0))
+                .changed();
+            if ui.button("🗑").clicked() {
+                to_remove = Some(preset.clone());
+            }
+        });
+        if !key.trim().is_empty() && !is_valid_asset_key(key.trim()) {
+            ui.colored_label(
+                egui::Color32::RED,
+                invalid_text.replace("{key}", key),
+            );
+        }
+    }
+
+    if let Some(preset) = to_remove {
+        app.rich_presence_template.preset_images.remove(&preset);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        let selected_text = if app.new_preset_image.is_empty() {
+            translate(app, "select_preset")
+        } else {
+            app.new_preset_image.clone()
+        };
+        egui::ComboBox::from_id_source("rich_presence_preset_image")
+            .selected_text(selected_text)
+            .width(180.0)
+            .show_ui(ui, |ui| {
+                for preset in &app.presets {
+                    if app.rich_presence_template.preset_images.contains_key(&preset.name) {
+                        continue;
+                    }
+                    ui.selectable_value(&mut app.new_preset_image, preset.name.clone(), &preset.name);
+                }
+            });
+
+        if ui
+            .add_enabled(
+                !app.new_preset_image.is_empty(),
+                egui::Button::new("➕ ".to_string() + &translate(app, "rp_preset_image_add")),
+            )
+            .clicked()
+        {
+            let preset = std::mem::take(&mut app.new_preset_image);
+            app.rich_presence_template
+                .preset_images
+                .insert(preset, String::new());
+        }
+    });
+
+    changed
+}
+
+/// Textos, imágenes y botones de la actividad
+fn render_activity(ui: &mut egui::Ui, app: &mut DsqApp) {
+    egui::CollapsingHeader::new(translate(app, "rp_activity"))
+        .id_source("rich_presence_template")
+        .show(ui, |ui| {
+            let mut changed = false;
+            let hint = translate(app, "launch_placeholders_hint")
+                .replace("{list}", &ACTIVITY_PLACEHOLDERS.join(" "));
+            ui.colored_label(egui::Color32::GRAY, hint);
+            ui.add_space(5.0);
+
+            egui::Grid::new("rich_presence_texts")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let fields = [
+                        ("rp_playing_details", "rp_details_playing"),
+                        ("rp_playing_state", "rp_state_playing"),
+                        ("rp_idle_details", "rp_details_idle"),
+                        ("rp_idle_state", "rp_state_idle"),
+                        ("rp_large_text_label", "rp_large_text"),
+                    ];
+                    for (label_key, default_key) in fields {
+                        let label = translate(app, label_key);
+                        let default = translate(app, default_key);
+                        let template = &mut app.rich_presence_template;
+                        let text = match label_key {
+                            "rp_playing_details" => &mut template.details,
+                            "rp_playing_state" => &mut template.state,
+                            "rp_idle_details" => &mut template.idle_details,
+                            "rp_idle_state" => &mut template.idle_state,
+                            _ => &mut template.large_text,
+                        };
+                        ui.label(label);
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(text)
+                                    .desired_width(220.0)
+                                    .hint_text(default),
+                            )
+                            .changed();
+                        ui.end_row();
+                    }
+
+                    ui.label(translate(app, "rp_large_image"));
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut app.rich_presence_template.large_image)
+                                .desired_width(220.0)
+                                .hint_text(DEFAULT_LARGE_IMAGE),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label(translate(app, "rp_small_image"));
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut app.rich_presence_template.small_image)
+                                .desired_width(220.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label(translate(app, "rp_small_text"));
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut app.rich_presence_template.small_text)
+                                .desired_width(220.0),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+
+            for key in [
+                &app.rich_presence_template.large_image,
+                &app.rich_presence_template.small_image,
+            ] {
+                let key = key.trim();
+                if !key.is_empty() && !is_valid_asset_key(key) {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        translate(app, "rp_invalid_asset_key").replace("{key}", key),
+                    );
+                }
+            }
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "rp_multi_label"));
+                let selected = app.rich_presence_template.multi_session;
+                egui::ComboBox::from_id_source("rich_presence_multi_session")
+                    .selected_text(translate(app, selected.lang_key()))
+                    .show_ui(ui, |ui| {
+                        for display in MultiSessionDisplay::ALL {
+                            let label = translate(app, display.lang_key());
+                            changed |= ui
+                                .selectable_value(
+                                    &mut app.rich_presence_template.multi_session,
+                                    display,
+                                    label,
+                                )
+                                .changed();
+                        }
+                    });
+            });
+
+            ui.add_space(5.0);
+            changed |= render_buttons(ui, app);
+
+            ui.add_space(5.0);
+            if ui
+                .button("↺ ".to_string() + &translate(app, "rp_reset"))
+                .clicked()
+            {
+                app.rich_presence_template = ActivityTemplate::default();
+                changed = true;
+            }
+
+            if changed {
+                app.update_rich_presence();
+            }
+        });
+}
+
+/// Lista de botones; al editarla deja de usarse el botón por defecto
+fn render_buttons(ui: &mut egui::Ui, app: &mut DsqApp) -> bool {
+    let mut buttons = app
+        .rich_presence_template
+        .buttons_or_default(|key| translate(app, key));
+    let mut changed = false;
+    let mut to_remove = None;
+
+    ui.label(translate(app, "rp_buttons"));
+    for (index, button) in buttons.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add(
+                    egui::TextEdit::singleline(&mut button.label)
+                        .desired_width(110.0)
+                        .hint_text(translate(app, "rp_button_label")),
+                )
+                .changed();
+            changed |= ui
+                .add(
+                    egui::TextEdit::singleline(&mut button.url)
+                        .desired_width(170.0)
+                        .hint_text("https://"),
+                )
+                .changed();
+            if ui.button("🗑").clicked() {
+                to_remove = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = to_remove {
+        buttons.remove(index);
+        changed = true;
+    }
+
+    // Discord admite como máximo dos botones
+    if buttons.len() < 2
+        && ui
+            .button("➕ ".to_string() + &translate(app, "rp_button_add"))
+            .clicked()
+    {
+        buttons.push(ActivityButton::default());
+        changed = true;
+    }
+
+    if changed {
+        app.rich_presence_template.buttons = Some(buttons);
+    }
+    changed
+}
@@ -1,8 +1,12 @@
 use eframe::egui;
 use crate::app::state::DsqApp;
 use crate::app::translate::translate;
-use crate::shared::types::Preset;
-use crate::core::presets::{ add_preset, edit_custom_preset, delete_custom_preset, load_presets };
+use crate::shared::types::{ ExecutableRole, ExecutableVariant, Preset, PresetExecutable };
+use crate::core::presets::{ add_preset, edit_custom_preset, delete_custom_preset };
+use crate::core::importer::{ import_detectable_file, ImportTarget };
+use crate::core::lint::canonical_tags;
+use crate::core::process::TEMPLATE_PLACEHOLDERS;
+use std::collections::BTreeMap;
 
 pub fn render_add_dialog(ctx: &egui::Context, app: &mut DsqApp) {
     if !app.show_add_preset_dialog {
@@ -69,6 +73,48 @@ pub fn render_delete_confirmation(ctx: &egui::Context, app: &mut DsqApp) {
         });
 }
 
+pub fn render_import_dialog(ctx: &egui::Context, app: &mut DsqApp) {
+    if !app.show_import_dialog {
+        return;
+    }
+
+    egui::Window
+        ::new(translate(app, "import_presets_title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical(|ui| {
+                ui.set_min_width(400.0);
+
+                ui.label(translate(app, "import_presets_help"));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(translate(app, "import_file_label"));
+                    ui.text_edit_singleline(&mut app.import_file_path);
+                });
+
+                ui.add_space(5.0);
+
+                let official_text = translate(app, "import_to_official");
+                ui.checkbox(&mut app.import_to_official, official_text);
+
+                ui.add_space(15.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("✔ ".to_string() + &translate(app, "import")).clicked() {
+                        handle_import_presets(app);
+                    }
+
+                    if ui.button("✖ ".to_string() + &translate(app, "cancel")).clicked() {
+                        close_import_dialog(app);
+                    }
+                });
+            });
+        });
+}
+
 fn render_preset_form(ui: &mut egui::Ui, app: &mut DsqApp, is_edit: bool) {
     ui.vertical(|ui| {
         ui.set_min_width(400.0);
@@ -81,17 +127,40 @@ fn render_preset_form(ui: &mut egui::Ui, app: &mut DsqApp, is_edit: bool) {
         ui.add_space(5.0);
 
         ui.horizontal(|ui| {
-            ui.label(translate(app, "executable_name"));
-            ui.text_edit_singleline(&mut app.new_preset_executable);
+            ui.label(translate(app, "preset_category"));
+            ui.text_edit_singleline(&mut app.new_preset_category);
         });
 
         ui.add_space(5.0);
 
         ui.horizontal(|ui| {
-            ui.label(translate(app, "path_label"));
-            ui.text_edit_singleline(&mut app.new_preset_path);
+            ui.label(translate(app, "preset_tags"));
+            ui.text_edit_singleline(&mut app.new_preset_tags)
+                .on_hover_text(translate(app, "preset_tags_hint"));
         });
 
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label(translate(app, "duration_label"));
+            ui.add(
+                egui::TextEdit
+                    ::singleline(&mut app.new_preset_duration)
+                    .desired_width(60.0)
+                    .hint_text(app.default_duration_minutes.to_string())
+            );
+        });
+
+        ui.add_space(5.0);
+
+        ui.add_space(5.0);
+
+        render_executables_editor(ui, app);
+
+        ui.add_space(5.0);
+
+        render_launch_options(ui, app);
+
         ui.add_space(15.0);
 
         ui.horizontal(|ui| {
@@ -114,23 +183,259 @@ fn render_preset_form(ui: &mut egui::Ui, app: &mut DsqApp, is_edit: bool) {
     });
 }
 
+fn render_executables_editor(ui: &mut egui::Ui, app: &mut DsqApp) {
+    if app.new_preset_executables.is_empty() {
+        app.new_preset_executables.push(PresetExecutable::default());
+    }
+
+    let executable_label = translate(app, "executable_name");
+    let path_label = translate(app, "path_label");
+    let role_labels: Vec<(ExecutableRole, String)> = ExecutableRole::ALL
+        .iter()
+        .map(|role| (*role, translate(app, role.lang_key())))
+        .collect();
+    let other_os_label = translate(app, "other_os_variants");
+    let can_remove = app.new_preset_executables.len() > 1;
+    let mut to_remove = None;
+
+    for (i, exe) in app.new_preset_executables.iter_mut().enumerate() {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(&executable_label);
+                ui.text_edit_singleline(&mut exe.executable);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(&path_label);
+                ui.text_edit_singleline(&mut exe.path);
+            });
+
+            ui.horizontal(|ui| {
+                let selected = role_labels
+                    .iter()
+                    .find(|(role, _)| *role == exe.role)
+                    .map(|(_, label)| label.clone())
+                    .unwrap_or_default();
+
+                egui::ComboBox
+                    ::from_id_source(("preset_executable_role", i))
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for (role, label) in &role_labels {
+                            ui.selectable_value(&mut exe.role, *role, label);
+                        }
+                    });
+
+                if can_remove && ui.button("🗑").clicked() {
+                    to_remove = Some(i);
+                }
+            });
+
+            egui::CollapsingHeader
+                ::new(&other_os_label)
+                .id_source(("preset_executable_variants", i))
+                .show(ui, |ui| {
+                    render_variant_fields(ui, "Linux", &mut exe.linux, &executable_label, &path_label);
+                    render_variant_fields(ui, "macOS", &mut exe.macos, &executable_label, &path_label);
+                });
+        });
+    }
+
+    if let Some(i) = to_remove {
+        app.new_preset_executables.remove(i);
+    }
+
+    if ui.button("➕ ".to_string() + &translate(app, "add_executable")).clicked() {
+        app.new_preset_executables.push(PresetExecutable {
+            role: ExecutableRole::Alternate,
+            ..Default::default()
+        });
+    }
+}
+
+/// Campos de la variante de un sistema. Si se deja vacía, se elimina.
+fn render_variant_fields(
+    ui: &mut egui::Ui,
+    os_label: &str,
+    variant: &mut Option<ExecutableVariant>,
+    executable_label: &str,
+    path_label: &str
+) {
+    let mut executable = variant
+        .as_ref()
+        .map(|v| v.executable.clone())
+        .unwrap_or_default();
+    let mut path = variant
+        .as_ref()
+        .and_then(|v| v.path.clone())
+        .unwrap_or_default();
+
+    ui.label(os_label);
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(executable_label);
+        changed |= ui.text_edit_singleline(&mut executable).changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label(path_label);
+        changed |= ui.text_edit_singleline(&mut path).changed();
+    });
+
+    if changed {
+        *variant = if executable.trim().is_empty() && path.trim().is_empty() {
+            None
+        } else {
+            Some(ExecutableVariant {
+                executable,
+                path: (!path.is_empty()).then_some(path),
+            })
+        };
+    }
+}
+
+fn render_launch_options(ui: &mut egui::Ui, app: &mut DsqApp) {
+    let hint = translate(app, "launch_placeholders_hint").replace(
+        "{list}",
+        &TEMPLATE_PLACEHOLDERS.join(" ")
+    );
+
+    egui::CollapsingHeader
+        ::new(translate(app, "launch_options"))
+        .id_source("preset_launch_options")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "launch_args"));
+                ui.text_edit_singleline(&mut app.new_preset_args);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "launch_cwd"));
+                ui.text_edit_singleline(&mut app.new_preset_cwd);
+            });
+
+            ui.label(translate(app, "launch_env"));
+            ui.add(
+                egui::TextEdit
+                    ::multiline(&mut app.new_preset_env)
+                    .desired_rows(2)
+                    .hint_text("KEY=VALUE")
+            );
+
+            ui.colored_label(egui::Color32::from_rgb(108, 117, 125), hint);
+        });
+}
+
+/// Separa argumentos por espacios, respetando comillas dobles
+pub fn split_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+
+    for c in text.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Une argumentos para editarlos, con comillas si tienen espacios
+pub fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("\"{}\"", arg)
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Interpreta líneas `CLAVE=VALOR`, ignorando las vacías
+fn parse_env(text: &str) -> BTreeMap<String, String> {
+    text.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+pub fn format_env(env: &BTreeMap<String, String>) -> String {
+    env.iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Verifica que el formulario tenga nombre y todos los ejecutables completos
+fn preset_form_is_valid(app: &DsqApp) -> bool {
+    !app.new_preset_name.trim().is_empty() &&
+        !app.new_preset_executables.is_empty() &&
+        app.new_preset_executables.iter().all(|exe| {
+            !exe.executable.trim().is_empty() &&
+                [&exe.linux, &exe.macos]
+                    .into_iter()
+                    .flatten()
+                    .all(|variant| !variant.executable.trim().is_empty())
+        })
+}
+
+/// Construye el preset personalizado con los datos del formulario
+fn preset_from_form(app: &DsqApp) -> Preset {
+    let tags: Vec<String> = app.new_preset_tags
+        .split(',')
+        .map(|tag| tag.to_string())
+        .collect();
+    let category = app.new_preset_category.trim();
+    let cwd = app.new_preset_cwd.trim();
+
+    Preset {
+        name: app.new_preset_name.clone(),
+        executables: app.new_preset_executables.clone(),
+        category: (!category.is_empty()).then(|| category.to_string()),
+        tags: canonical_tags(&tags),
+        args: split_args(&app.new_preset_args),
+        cwd: (!cwd.is_empty()).then(|| cwd.to_string()),
+        env: parse_env(&app.new_preset_env),
+        duration_minutes: app.new_preset_duration
+            .trim()
+            .parse()
+            .ok()
+            .filter(|minutes| *minutes > 0),
+        is_custom: true,
+    }
+}
+
 fn handle_save_preset(app: &mut DsqApp) {
-    if app.new_preset_name.trim().is_empty() || app.new_preset_executable.trim().is_empty() {
+    if !preset_form_is_valid(app) {
         app.status = translate(app, "preset_fields_empty");
         return;
     }
 
-    let new_preset = Preset {
-        name: app.new_preset_name.clone(),
-        executable: app.new_preset_executable.clone(),
-        path: app.new_preset_path.clone(),
-        is_custom: true,
-    };
+    let new_preset = preset_from_form(app);
 
     match add_preset(new_preset) {
         Ok(_) => {
-            app.presets = load_presets();
-            app.filtered_presets = app.presets.clone();
+            app.reload_presets();
             app.status = translate(app, "preset_added_success");
             close_add_dialog(app);
         }
@@ -141,23 +446,17 @@ fn handle_save_preset(app: &mut DsqApp) {
 }
 
 fn handle_edit_preset(app: &mut DsqApp) {
-    if app.new_preset_name.trim().is_empty() || app.new_preset_executable.trim().is_empty() {
+    if !preset_form_is_valid(app) {
         app.status = translate(app, "preset_fields_empty");
         return;
     }
 
     if let Some(ref old_name) = app.preset_to_edit {
-        let edited_preset = Preset {
-            name: app.new_preset_name.clone(),
-            executable: app.new_preset_executable.clone(),
-            path: app.new_preset_path.clone(),
-            is_custom: true,
-        };
+        let edited_preset = preset_from_form(app);
 
         match edit_custom_preset(old_name, edited_preset) {
             Ok(_) => {
-                app.presets = load_presets();
-                app.filtered_presets = app.presets.clone();
+                app.reload_presets();
                 app.status = translate(app, "preset_edited_success");
                 close_edit_dialog(app);
             }
@@ -172,8 +471,7 @@ fn handle_delete_preset(app: &mut DsqApp) {
     if let Some(ref name) = app.preset_to_delete {
         match delete_custom_preset(name) {
             Ok(_) => {
-                app.presets = load_presets();
-                app.filtered_presets = app.presets.clone();
+                app.reload_presets();
                 app.status = translate(app, "preset_deleted_success");
                 close_delete_dialog(app);
             }
@@ -184,22 +482,62 @@ fn handle_delete_preset(app: &mut DsqApp) {
     }
 }
 
+fn handle_import_presets(app: &mut DsqApp) {
+    let path = app.import_file_path.trim().trim_matches('"').to_string();
+    if path.is_empty() {
+        app.status = translate(app, "import_file_empty");
+        return;
+    }
+
+    let target = if app.import_to_official { ImportTarget::Official } else { ImportTarget::Custom };
+
+    match import_detectable_file(&path, target) {
+        Ok(report) => {
+            app.reload_presets();
+            app.status = translate(app, "import_success")
+                .replace("{added}", &report.added.to_string())
+                .replace("{duplicates}", &report.duplicates.to_string())
+                .replace("{skipped}", &report.skipped.to_string());
+            close_import_dialog(app);
+        }
+        Err(e) => {
+            app.status = translate(app, "error").replace("{error}", &e.to_string());
+        }
+    }
+}
+
 fn close_add_dialog(app: &mut DsqApp) {
     app.show_add_preset_dialog = false;
     app.new_preset_name.clear();
-    app.new_preset_executable.clear();
-    app.new_preset_path.clear();
+    app.new_preset_executables.clear();
+    app.new_preset_category.clear();
+    app.new_preset_tags.clear();
+    app.new_preset_args.clear();
+    app.new_preset_cwd.clear();
+    app.new_preset_env.clear();
+    app.new_preset_duration.clear();
 }
 
 fn close_edit_dialog(app: &mut DsqApp) {
     app.show_edit_preset_dialog = false;
     app.preset_to_edit = None;
     app.new_preset_name.clear();
-    app.new_preset_executable.clear();
-    app.new_preset_path.clear();
+    app.new_preset_executables.clear();
+    app.new_preset_category.clear();
+    app.new_preset_tags.clear();
+    app.new_preset_args.clear();
+    app.new_preset_cwd.clear();
+    app.new_preset_env.clear();
+    app.new_preset_duration.clear();
 }
 
 fn close_delete_dialog(app: &mut DsqApp) {
     app.show_delete_confirmation = false;
     app.preset_to_delete = None;
 }
+
+fn close_import_dialog(app: &mut DsqApp) {
+    app.show_import_dialog = false;
+    app.import_file_path.clear();
+    app.import_to_official = false;
+}
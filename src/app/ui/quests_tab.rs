@@ -0,0 +1,252 @@
+use super::components;
+use super::main_tab::start_preset;
+use crate::app::state::DsqApp;
+use crate::app::translate::translate;
+use crate::core::quests::{next_quest_id, save_quests, sort_quests, Quest};
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+
+/// Acción elegida en la lista, se aplica después de dibujarla
+enum QuestAction {
+    Start(usize),
+    Delete(usize),
+}
+
+pub fn render(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.vertical_centered(|ui| {
+        ui.heading(translate(app, "quests"));
+        ui.add_space(10.0);
+
+        if let Some(error) = &app.quests_load_error {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 193, 7),
+                translate(app, "quests_load_failed").replace("{error}", error),
+            );
+            ui.add_space(10.0);
+        }
+
+        render_add_quest(ui, app);
+        ui.add_space(10.0);
+        render_quest_list(ui, app);
+
+        if !app.status.is_empty() {
+            ui.add_space(10.0);
+            components::status_card(ui, &app.status);
+        }
+    });
+}
+
+fn render_add_quest(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "quest_preset"));
+                let selected_text = if app.new_quest_preset.is_empty() {
+                    translate(app, "select_preset")
+                } else {
+                    app.new_quest_preset.clone()
+                };
+                egui::ComboBox::from_id_source("quest_preset")
+                    .selected_text(selected_text)
+                    .width(220.0)
+                    .show_ui(ui, |ui| {
+                        for preset in &app.presets {
+                            if ui
+                                .selectable_label(app.new_quest_preset == preset.name, &preset.name)
+                                .clicked()
+                            {
+                                app.new_quest_preset = preset.name.clone();
+                                if let Some(minutes) = preset.duration_minutes {
+                                    app.new_quest_minutes = minutes;
+                                }
+                            }
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "quest_required"));
+                ui.add(
+                    egui::DragValue::new(&mut app.new_quest_minutes)
+                        .clamp_range(1..=1440)
+                        .suffix(" min"),
+                );
+
+                ui.label(translate(app, "quest_expires"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut app.new_quest_expires)
+                        .desired_width(90.0)
+                        .hint_text("YYYY-MM-DD"),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(translate(app, "quest_notes"));
+                ui.text_edit_singleline(&mut app.new_quest_notes);
+            });
+
+            ui.add_space(5.0);
+
+            if ui
+                .button("➕ ".to_string() + &translate(app, "quest_add"))
+                .clicked()
+            {
+                handle_add_quest(app);
+            }
+        });
+    });
+}
+
+fn handle_add_quest(app: &mut DsqApp) {
+    if app.new_quest_preset.is_empty() {
+        app.status = translate(app, "quest_no_preset");
+        return;
+    }
+
+    let expires_text = app.new_quest_expires.trim();
+    let expires = if expires_text.is_empty() {
+        None
+    } else {
+        match NaiveDate::parse_from_str(expires_text, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                app.status = translate(app, "invalid_date");
+                return;
+            }
+        }
+    };
+
+    app.quests.push(Quest {
+        id: next_quest_id(&app.quests),
+        preset: app.new_quest_preset.clone(),
+        required_minutes: app.new_quest_minutes.max(1),
+        expires,
+        notes: app.new_quest_notes.trim().to_string(),
+        progress_seconds: 0,
+        completed_at: None,
+    });
+    sort_quests(&mut app.quests);
+    log::info!("Added quest for {}", app.new_quest_preset);
+
+    app.new_quest_preset.clear();
+    app.new_quest_expires.clear();
+    app.new_quest_notes.clear();
+    save_quests_or_report(app);
+}
+
+fn render_quest_list(ui: &mut egui::Ui, app: &mut DsqApp) {
+    if app.quests.is_empty() {
+        ui.colored_label(egui::Color32::GRAY, translate(app, "quests_empty"));
+        return;
+    }
+
+    let today = Local::now().date_naive();
+    let mut action = None;
+
+    egui::ScrollArea::vertical()
+        .max_height(220.0)
+        .show(ui, |ui| {
+            for (index, quest) in app.quests.iter().enumerate() {
+                ui.group(|ui| {
+                    ui.set_min_width(350.0);
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.strong(&quest.preset);
+                            if let Some(expires) = quest.expires {
+                                let (color, key) = if quest.is_expired(today) {
+                                    (egui::Color32::RED, "quest_expired")
+                                } else if (expires - today).num_days() <= 1 {
+                                    (egui::Color32::from_rgb(255, 193, 7), "quest_expires_on")
+                                } else {
+                                    (egui::Color32::GRAY, "quest_expires_on")
+                                };
+                                ui.colored_label(
+                                    color,
+                                    translate(app, key).replace("{date}", &expires.to_string()),
+                                );
+                            }
+                        });
+
+                        if !quest.notes.is_empty() {
+                            ui.colored_label(egui::Color32::from_rgb(108, 117, 125), &quest.notes);
+                        }
+
+                        ui.add(
+                            egui::ProgressBar::new(quest.progress())
+                                .desired_width(300.0)
+                                .text(format!(
+                                    "{} / {} min",
+                                    quest.progress_seconds / 60,
+                                    quest.required_minutes
+                                )),
+                        );
+
+                        ui.horizontal(|ui| {
+                            if quest.is_complete() {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(40, 167, 69),
+                                    translate(app, "quest_done"),
+                                );
+                            } else if !quest.is_expired(today)
+                                && ui
+                                    .button(
+                                        translate(app, "quest_start").replace(
+                                            "{minutes}",
+                                            &quest.remaining_minutes().to_string(),
+                                        ),
+                                    )
+                                    .clicked()
+                            {
+                                action = Some(QuestAction::Start(index));
+                            }
+
+                            if ui.button("🗑").clicked() {
+                                action = Some(QuestAction::Delete(index));
+                            }
+                        });
+                    });
+                });
+            }
+        });
+
+    match action {
+        Some(QuestAction::Start(index)) => handle_start_quest(app, index),
+        Some(QuestAction::Delete(index)) => {
+            let quest = app.quests.remove(index);
+            log::info!("Deleted quest {} ({})", quest.id, quest.preset);
+            save_quests_or_report(app);
+        }
+        None => {}
+    }
+}
+
+/// Inicia el preset de la misión con el tiempo que le falta
+fn handle_start_quest(app: &mut DsqApp, index: usize) {
+    let quest = &app.quests[index];
+    let minutes = quest.remaining_minutes().max(1);
+
+    match app
+        .presets
+        .iter()
+        .position(|preset| preset.name.eq_ignore_ascii_case(&quest.preset))
+    {
+        Some(preset_index) => {
+            start_preset(app, preset_index, minutes);
+        }
+        None => {
+            app.status = translate(app, "preset_not_found").replace("{name}", &quest.preset);
+        }
+    }
+}
+
+fn save_quests_or_report(app: &mut DsqApp) {
+    if app.quests_load_error.is_some() {
+        app.status = translate(app, "quests_not_saved");
+        return;
+    }
+    if let Err(e) = save_quests(&app.quests) {
+        log::error!("Failed to save quests: {}", e);
+        app.status = translate(app, "error").replace("{error}", &e.to_string());
+    }
+}
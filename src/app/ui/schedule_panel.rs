@@ -0,0 +1,211 @@
+use super::settings_tab::save_settings_config;
+use crate::app::state::DsqApp;
+use crate::app::translate::translate;
+use crate::core::scheduler::{
+    next_schedule_id, upcoming_runs, MissedRunPolicy, Repeat, ScheduledRun,
+};
+use chrono::{Local, NaiveDate, NaiveTime, Weekday};
+use eframe::egui;
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+fn weekday_key(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "day_mon",
+        Weekday::Tue => "day_tue",
+        Weekday::Wed => "day_wed",
+        Weekday::Thu => "day_thu",
+        Weekday::Fri => "day_fri",
+        Weekday::Sat => "day_sat",
+        Weekday::Sun => "day_sun",
+    }
+}
+
+pub fn render(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.add_space(10.0);
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            egui::CollapsingHeader::new(format!(
+                "{} ({})",
+                translate(app, "schedules"),
+                app.schedules.len()
+            ))
+            .id_source("scheduled_runs")
+            .show(ui, |ui| {
+                render_upcoming_runs(ui, app);
+                ui.add_space(5.0);
+                ui.separator();
+                render_schedule_form(ui, app);
+            });
+        });
+    });
+}
+
+fn render_upcoming_runs(ui: &mut egui::Ui, app: &mut DsqApp) {
+    if app.headless_scheduler {
+        ui.colored_label(
+            egui::Color32::from_rgb(108, 117, 125),
+            translate(app, "schedules_headless"),
+        );
+        ui.add_space(5.0);
+    }
+
+    if app.schedules.is_empty() {
+        ui.colored_label(egui::Color32::GRAY, translate(app, "schedules_empty"));
+        return;
+    }
+
+    let mut to_delete = None;
+    for (schedule, when) in upcoming_runs(&app.schedules, Local::now()) {
+        ui.horizontal(|ui| {
+            ui.label(when.format("%Y-%m-%d %H:%M").to_string());
+            ui.strong(&schedule.preset);
+            ui.colored_label(
+                egui::Color32::from_rgb(108, 117, 125),
+                format!(
+                    "{} min · {}",
+                    schedule.duration_minutes,
+                    translate(app, schedule.repeat.lang_key())
+                ),
+            );
+            if ui.button("🗑").clicked() {
+                to_delete = Some(schedule.id);
+            }
+        });
+    }
+
+    if let Some(id) = to_delete {
+        app.schedules.retain(|schedule| schedule.id != id);
+        log::info!("Deleted scheduled run {}", id);
+        save_settings_config(app);
+    }
+}
+
+fn render_schedule_form(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("schedule_repeat")
+            .selected_text(translate(app, app.new_schedule_repeat.lang_key()))
+            .show_ui(ui, |ui| {
+                for repeat in Repeat::ALL {
+                    let label = translate(app, repeat.lang_key());
+                    ui.selectable_value(&mut app.new_schedule_repeat, repeat, label);
+                }
+            });
+
+        match app.new_schedule_repeat {
+            Repeat::Once => {
+                ui.add(
+                    egui::TextEdit::singleline(&mut app.new_schedule_date)
+                        .desired_width(90.0)
+                        .hint_text("YYYY-MM-DD"),
+                );
+            }
+            Repeat::Daily => {}
+            Repeat::Weekly => {
+                let weekday = app.new_schedule_weekday.unwrap_or(Weekday::Mon);
+                egui::ComboBox::from_id_source("schedule_weekday")
+                    .selected_text(translate(app, weekday_key(weekday)))
+                    .show_ui(ui, |ui| {
+                        for day in WEEKDAYS {
+                            if ui
+                                .selectable_label(weekday == day, translate(app, weekday_key(day)))
+                                .clicked()
+                            {
+                                app.new_schedule_weekday = Some(day);
+                            }
+                        }
+                    });
+            }
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut app.new_schedule_time)
+                .desired_width(50.0)
+                .hint_text("HH:MM"),
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.label(translate(app, "duration_label"));
+        ui.add(
+            egui::DragValue::new(&mut app.new_schedule_minutes)
+                .clamp_range(1..=1440)
+                .suffix(" min"),
+        );
+
+        ui.label(translate(app, "schedule_missed_label"));
+        egui::ComboBox::from_id_source("schedule_missed")
+            .selected_text(translate(app, app.new_schedule_missed.lang_key()))
+            .show_ui(ui, |ui| {
+                for policy in MissedRunPolicy::ALL {
+                    let label = translate(app, policy.lang_key());
+                    ui.selectable_value(&mut app.new_schedule_missed, policy, label);
+                }
+            });
+    });
+
+    if ui
+        .button("➕ ".to_string() + &translate(app, "schedule_add"))
+        .clicked()
+    {
+        handle_add_schedule(app);
+    }
+}
+
+/// Programa el preset seleccionado con los valores del formulario
+fn handle_add_schedule(app: &mut DsqApp) {
+    let Some(preset) = app.presets.get(app.selected_preset) else {
+        return;
+    };
+    let preset = preset.name.clone();
+
+    let Ok(time) = NaiveTime::parse_from_str(app.new_schedule_time.trim(), "%H:%M") else {
+        app.status = translate(app, "schedule_invalid_time");
+        return;
+    };
+
+    let date = match app.new_schedule_repeat {
+        Repeat::Once => match NaiveDate::parse_from_str(app.new_schedule_date.trim(), "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                app.status = translate(app, "invalid_date");
+                return;
+            }
+        },
+        _ => None,
+    };
+
+    let schedule = ScheduledRun {
+        id: next_schedule_id(&app.schedules),
+        preset,
+        duration_minutes: app.new_schedule_minutes.max(1),
+        repeat: app.new_schedule_repeat,
+        date,
+        weekday: (app.new_schedule_repeat == Repeat::Weekly)
+            .then(|| app.new_schedule_weekday.unwrap_or(Weekday::Mon)),
+        time,
+        missed: app.new_schedule_missed,
+        last_run: Local::now(),
+    };
+
+    if schedule.next_occurrence(Local::now()).is_none() {
+        app.status = translate(app, "schedule_in_past");
+        return;
+    }
+
+    log::info!("Added scheduled run {} for {}", schedule.id, schedule.preset);
+    app.status = translate(app, "schedule_added").replace("{name}", &schedule.preset);
+    app.schedules.push(schedule);
+    app.new_schedule_date.clear();
+    app.new_schedule_time.clear();
+    save_settings_config(app);
+}
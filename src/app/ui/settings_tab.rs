@@ -1,22 +1,43 @@
+use crate::app::api::API_TOKEN_FILE;
+use super::presence_panel;
 use crate::app::state::DsqApp;
 use crate::app::translate::translate;
-use crate::shared::config::save_config;
-use crate::shared::richpresence::RichPresenceManager;
-use crate::shared::types::Config;
+use crate::core::scheduler::merge_last_runs;
+use crate::shared::config::{load_config, save_config};
+use crate::platform::clients::BUILTIN_CLIENTS;
+use crate::shared::types::{Config, CustomClient};
 use eframe::egui;
 
 pub fn render(ui: &mut egui::Ui, app: &mut DsqApp) {
-    ui.vertical_centered(|ui| {
-        ui.heading(translate(app, "settings"));
-        ui.add_space(20.0);
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        ui.vertical_centered(|ui| {
+            ui.heading(translate(app, "settings"));
+            ui.add_space(20.0);
+
+            if let Some(error) = &app.config_load_error {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 193, 7),
+                    translate(app, "config_load_failed").replace("{error}", error),
+                );
+                ui.add_space(15.0);
+            }
 
-        render_language_settings(ui, app);
-        ui.add_space(15.0);
-        render_rich_presence_settings(ui, app);
-        ui.add_space(20.0);
-        render_settings_notice(ui, app);
+            render_language_settings(ui, app);
+            ui.add_space(15.0);
+            render_rich_presence_settings(ui, app);
+            ui.add_space(15.0);
+            render_simulation_settings(ui, app);
+            ui.add_space(15.0);
+            render_automation_settings(ui, app);
+            ui.add_space(15.0);
+            render_client_settings(ui, app);
+            ui.add_space(15.0);
+            render_api_settings(ui, app);
+            ui.add_space(20.0);
+            render_settings_notice(ui, app);
 
-        save_settings_config(app);
+            save_settings_config(app);
+        });
     });
 }
 
@@ -27,6 +48,7 @@ fn render_language_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
             ui.heading(translate(app, "language"));
             ui.add_space(10.0);
 
+            let previous_lang = app.selected_lang.clone();
             ui.vertical_centered(|ui| {
                 ui.horizontal(|ui| {
                     ui.add_space(150.0);
@@ -40,6 +62,11 @@ fn render_language_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
                         });
                 });
             });
+
+            // Los textos por defecto de Rich Presence dependen del idioma
+            if app.selected_lang != previous_lang {
+                app.update_rich_presence();
+            }
         });
     });
 }
@@ -51,41 +78,181 @@ fn render_rich_presence_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
             ui.heading("🎮 Discord Rich Presence");
             ui.add_space(10.0);
 
-            let mut rich_presence_changed = false;
+            // El hilo de Rich Presence se inicia o detiene en el próximo cuadro
             let enable_rich_presence_text = translate(app, "enable_rich_presence");
+            ui.checkbox(&mut app.rich_presence_enabled, &enable_rich_presence_text);
+
+            if app.rich_presence_enabled {
+                ui.add_space(5.0);
+                presence_panel::render(ui, app);
+            }
+        });
+    });
+}
+
+fn render_simulation_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.heading(translate(app, "simulation_settings"));
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.add_space(90.0);
+                ui.label(translate(app, "default_duration"));
+                ui.add(
+                    egui::DragValue::new(&mut app.default_duration_minutes)
+                        .clamp_range(1..=1440)
+                        .suffix(" min"),
+                );
+            });
+        });
+    });
+}
+
+fn render_automation_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.heading(translate(app, "automation"));
+            ui.add_space(10.0);
+
+            let auto_start_text = translate(app, "auto_start_on_discord");
+            ui.checkbox(&mut app.auto_start.enabled, auto_start_text);
+
+            let last_used_text = translate(app, "last_used_preset");
+            ui.add_enabled_ui(app.auto_start.enabled, |ui| {
+                egui::ComboBox::from_id_source("auto_start_preset")
+                    .selected_text(app.auto_start.preset.clone().unwrap_or(last_used_text.clone()))
+                    .width(220.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut app.auto_start.preset, None, last_used_text);
+                        for preset in &app.presets {
+                            ui.selectable_value(
+                                &mut app.auto_start.preset,
+                                Some(preset.name.clone()),
+                                &preset.name,
+                            );
+                        }
+                    });
+            });
+
+            ui.add_space(5.0);
+            let stop_text = translate(app, "stop_on_discord_exit");
+            ui.checkbox(&mut app.auto_start.stop_on_discord_exit, stop_text);
+        });
+    });
+}
+
+/// Clientes de Discord del usuario: otra carpeta de instalación o un
+/// cliente que no está en la lista de conocidos
+fn render_client_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.heading(translate(app, "discord_clients"));
+            ui.add_space(10.0);
+
+            let known: Vec<&str> = BUILTIN_CLIENTS.iter().map(|client| client.name).collect();
+            ui.colored_label(
+                egui::Color32::GRAY,
+                translate(app, "discord_clients_known").replace("{list}", &known.join(", ")),
+            );
+            ui.add_space(5.0);
+
+            let name_text = translate(app, "custom_client_name");
+            let processes_text = translate(app, "custom_client_processes");
+            let command_text = translate(app, "custom_client_command");
+            let mut changed = false;
+            let mut to_remove = None;
+
+            for (index, client) in app.custom_clients.iter_mut().enumerate() {
+                egui::Grid::new(("custom_client", index))
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(&name_text);
+                        ui.horizontal(|ui| {
+                            changed |= ui
+                                .add(egui::TextEdit::singleline(&mut client.name).desired_width(180.0))
+                                .lost_focus();
+                            if ui.button("🗑").clicked() {
+                                to_remove = Some(index);
+                            }
+                        });
+                        ui.end_row();
+
+                        // Separados por comas; se limpian al armar el registro
+                        ui.label(&processes_text);
+                        let mut processes = client.process_names.join(",");
+                        let response =
+                            ui.add(egui::TextEdit::singleline(&mut processes).desired_width(210.0));
+                        if response.changed() {
+                            client.process_names = processes.split(',').map(str::to_string).collect();
+                        }
+                        changed |= response.lost_focus();
+                        ui.end_row();
+
+                        ui.label(&command_text);
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut client.launch_command)
+                                    .desired_width(210.0),
+                            )
+                            .lost_focus();
+                        ui.end_row();
+                    });
+                ui.add_space(5.0);
+            }
+
+            if let Some(index) = to_remove {
+                app.custom_clients.remove(index);
+                changed = true;
+            }
 
             if ui
-                .checkbox(&mut app.rich_presence_enabled, &enable_rich_presence_text)
-                .changed()
+                .button("➕ ".to_string() + &translate(app, "custom_client_add"))
+                .clicked()
             {
-                rich_presence_changed = true;
+                app.custom_clients.push(CustomClient::default());
             }
 
-            if rich_presence_changed {
-                handle_rich_presence_toggle(app);
+            // Volver a buscar Discord con el registro actualizado al
+            // terminar de editar, no con cada tecla
+            if changed {
+                app.invalidate_discord_cache();
             }
         });
     });
 }
 
-fn handle_rich_presence_toggle(app: &mut DsqApp) {
-    if app.rich_presence_enabled {
-        if app.rich_presence.is_none() {
-            let mut rp = RichPresenceManager::new();
-            if let Ok(()) = rp.connect() {
-                let activity = app.current_simulated_game.clone();
-                let _ = rp.set_activity(activity);
-                app.rich_presence = Some(rp);
-            } else {
-                app.status =
-                    translate(app, "rich_presence_error").replace("{error}", "No se pudo conectar");
-                app.rich_presence_enabled = false;
+fn render_api_settings(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.heading(translate(app, "local_api"));
+            ui.add_space(10.0);
+
+            let enabled_text = translate(app, "api_enabled");
+            ui.checkbox(&mut app.api.enabled, enabled_text);
+
+            ui.add_enabled_ui(app.api.enabled && app.api.unix_socket.is_none(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(translate(app, "api_port"));
+                    ui.add(egui::DragValue::new(&mut app.api.port).clamp_range(1024..=65535));
+                });
+            });
+
+            if let Some(socket) = &app.api.unix_socket {
+                ui.label(translate(app, "api_socket").replace("{path}", socket));
             }
-        }
-    } else if let Some(mut rp) = app.rich_presence.take() {
-        let _ = rp.clear_activity();
-        rp.disconnect();
-    }
+
+            ui.colored_label(
+                egui::Color32::from_rgb(108, 117, 125),
+                translate(app, "api_token_file").replace("{path}", API_TOKEN_FILE),
+            );
+            ui.colored_label(egui::Color32::GRAY, translate(app, "api_restart_notice"));
+        });
+    });
 }
 
 fn render_settings_notice(ui: &mut egui::Ui, app: &mut DsqApp) {
@@ -94,13 +261,44 @@ fn render_settings_notice(ui: &mut egui::Ui, app: &mut DsqApp) {
     ui.colored_label(egui::Color32::GRAY, translate(app, "settings_notice"));
 }
 
-fn save_settings_config(app: &DsqApp) {
-    let config = Config {
+/// Guarda la configuración actual de la app, salvo que config.json
+/// no se haya podido leer: se perderían los ajustes del usuario
+pub fn save_settings_config(app: &mut DsqApp) {
+    if app.config_load_error.is_some() {
+        return;
+    }
+
+    let mut config = Config {
         language: app.selected_lang.clone(),
         selected_preset: app.selected_preset,
         process_name: app.process_name.clone(),
         custom_path: app.custom_path.clone(),
         rich_presence_enabled: app.rich_presence_enabled,
+        rich_presence_template: app.rich_presence_template.clone(),
+        rich_presence_client_id: app.rich_presence_client_id.clone(),
+        default_duration_minutes: app.default_duration_minutes,
+        schedules: app.schedules.clone(),
+        auto_start: app.auto_start.clone(),
+        launch_start_preset: app.launch_start_preset,
+        last_used_preset: app.last_used_preset.clone(),
+        api: app.api.clone(),
+        hooks: app.hooks.clone(),
+        custom_clients: app.custom_clients.clone(),
     };
-    save_config(&config);
+
+    let json = serde_json::to_string(&config).unwrap_or_default();
+    if json == app.saved_config {
+        return;
+    }
+
+    // `scheduler run` guarda sus ejecuciones en config.json; no pisarlas
+    if app.headless_scheduler {
+        if let Ok(saved) = load_config() {
+            merge_last_runs(&mut config.schedules, &saved.schedules);
+        }
+    }
+
+    if save_config(&config) {
+        app.saved_config = json;
+    }
 }
@@ -0,0 +1,130 @@
+use super::components;
+use crate::app::state::DsqApp;
+use crate::app::translate::translate;
+use crate::core::history::{
+    export_history, format_duration, totals_per_game, totals_per_week, ExportFormat, Totals,
+};
+use crate::core::presets::write_atomic;
+use eframe::egui;
+
+/// Cantidad de sesiones recientes que se muestran
+const RECENT_SESSIONS: usize = 5;
+
+pub fn render(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.vertical_centered(|ui| {
+        ui.heading(translate(app, "stats"));
+        ui.add_space(10.0);
+
+        if app.session_history.is_empty() {
+            ui.colored_label(egui::Color32::GRAY, translate(app, "stats_empty"));
+        } else {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    render_totals(ui, app, "stats_per_game", &totals_per_game(&app.session_history));
+                    ui.add_space(10.0);
+                    render_totals(ui, app, "stats_per_week", &totals_per_week(&app.session_history));
+                    ui.add_space(10.0);
+                    render_recent_sessions(ui, app);
+                });
+        }
+
+        ui.add_space(10.0);
+        render_export_buttons(ui, app);
+
+        if !app.status.is_empty() {
+            ui.add_space(10.0);
+            components::status_card(ui, &app.status);
+        }
+    });
+}
+
+fn render_totals(ui: &mut egui::Ui, app: &DsqApp, title_key: &str, totals: &[Totals]) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.strong(translate(app, title_key));
+            ui.add_space(5.0);
+
+            let sessions_text = translate(app, "stats_sessions");
+            egui::Grid::new(title_key)
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for t in totals {
+                        ui.label(&t.label);
+                        ui.label(format_duration(t.seconds));
+                        ui.colored_label(
+                            egui::Color32::from_rgb(108, 117, 125),
+                            sessions_text.replace("{count}", &t.sessions.to_string()),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+    });
+}
+
+fn render_recent_sessions(ui: &mut egui::Ui, app: &DsqApp) {
+    ui.group(|ui| {
+        ui.set_min_width(350.0);
+        ui.vertical_centered(|ui| {
+            ui.strong(translate(app, "stats_recent"));
+            ui.add_space(5.0);
+
+            egui::Grid::new("recent_sessions")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    for record in app.session_history.iter().rev().take(RECENT_SESSIONS) {
+                        ui.label(&record.preset);
+                        ui.label(record.started_at.format("%Y-%m-%d %H:%M").to_string());
+                        ui.label(format_duration(record.duration_seconds));
+                        ui.colored_label(
+                            egui::Color32::from_rgb(108, 117, 125),
+                            translate(app, record.exit_reason.lang_key()),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+    });
+}
+
+fn render_export_buttons(ui: &mut egui::Ui, app: &mut DsqApp) {
+    ui.horizontal(|ui| {
+        ui.add_space(((ui.available_width() - 250.0) / 2.0).max(0.0));
+
+        if ui
+            .button("📤 ".to_string() + &translate(app, "export_csv"))
+            .clicked()
+        {
+            handle_export(app, ExportFormat::Csv);
+        }
+
+        if ui
+            .button("📤 ".to_string() + &translate(app, "export_json"))
+            .clicked()
+        {
+            handle_export(app, ExportFormat::Json);
+        }
+    });
+}
+
+fn handle_export(app: &mut DsqApp, format: ExportFormat) {
+    let file = format!("session_history_export.{}", format.extension());
+
+    let result = export_history(&app.session_history, format)
+        .and_then(|data| Ok(write_atomic(&file, data.as_bytes())?));
+
+    match result {
+        Ok(()) => {
+            log::info!("Exported {} sessions to {}", app.session_history.len(), file);
+            app.status = translate(app, "export_success").replace("{path}", &file);
+        }
+        Err(e) => {
+            log::error!("Failed to export session history: {}", e);
+            app.status = translate(app, "error").replace("{error}", &e.to_string());
+        }
+    }
+}
@@ -2,7 +2,14 @@
 
 use eframe::App;
 use eframe::{egui, Frame};
-use std::{collections::HashMap, env, fs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+/// Códigos de salida que DSQProcess usa para el historial
+/// (deben coincidir con core::history)
+const EXIT_SCHEDULED: i32 = 0;
+const EXIT_MANUAL: i32 = 10;
 
 type LangMap = HashMap<String, String>;
 
@@ -11,8 +18,24 @@ struct Config {
     language: String,
 }
 
+/// Carpeta de DSQProcess: el proceso puede iniciarse con otro directorio de trabajo
+fn app_dir() -> PathBuf {
+    env::var_os("DSQ_APP_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
+
+/// Duración de la simulación indicada por DSQProcess (15 minutos por defecto)
+fn duration_minutes() -> u64 {
+    env::var("DSQ_DURATION_MINUTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(15)
+}
+
 fn load_language_from_config() -> LangMap {
-    let config: Config = fs::read_to_string("config.json")
+    let config: Config = fs::read_to_string(app_dir().join("config.json"))
         .ok()
         .and_then(|data| serde_json::from_str(&data).ok())
         .unwrap_or(Config {
@@ -24,7 +47,7 @@ fn load_language_from_config() -> LangMap {
     } else {
         "es"
     };
-    let path = format!("lang/{}_child.json", lang_code);
+    let path = app_dir().join(format!("lang/{}_child.json", lang_code));
     let data = fs::read_to_string(path).unwrap_or_default();
     serde_json::from_str(&data).unwrap_or_default()
 }
@@ -36,6 +59,9 @@ struct DsqChildApp {
     process_name: String,
     start_time: std::time::Instant,
     is_scheduled_to_close: bool,
+    duration_minutes: u64,
+    /// Se marca cuando la ventana se cierra por el cierre programado
+    closed_by_schedule: Arc<AtomicBool>,
 }
 
 impl Default for DsqChildApp {
@@ -48,13 +74,18 @@ impl Default for DsqChildApp {
             .unwrap_or("simulated_process")
             .to_string();
 
+        let duration_minutes = duration_minutes();
+        let start_time = std::time::Instant::now();
+
         Self {
-            closing_time: None,
+            closing_time: Some(start_time + std::time::Duration::from_secs(duration_minutes * 60)),
             status: String::new(),
             lang,
             process_name,
-            start_time: std::time::Instant::now(),
-            is_scheduled_to_close: false,
+            start_time,
+            is_scheduled_to_close: true,
+            duration_minutes,
+            closed_by_schedule: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -67,6 +98,11 @@ impl DsqChildApp {
             .unwrap_or_else(|| key.to_string())
     }
 
+    fn t_minutes(&self, key: &str) -> String {
+        self.t(key)
+            .replace("{minutes}", &self.duration_minutes.to_string())
+    }
+
     fn format_duration(&self, duration: std::time::Duration) -> String {
         let total_seconds = duration.as_secs();
         let hours = total_seconds / 3600;
@@ -125,6 +161,7 @@ impl App for DsqChildApp {
                         if let Some(when) = self.closing_time {
                             let now = std::time::Instant::now();
                             if now >= when {
+                                self.closed_by_schedule.store(true, Ordering::Relaxed);
                                 ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                             } else {
                                 let remaining = when.duration_since(now);
@@ -150,16 +187,17 @@ impl App for DsqChildApp {
                     ui.horizontal(|ui| {
                         ui.add_space(20.0);
                         if !self.is_scheduled_to_close {
-                            let button_15 = ui.add_sized(
+                            let button_schedule = ui.add_sized(
                                 [140.0, 35.0],
-                                egui::Button::new(format!("⏱ {}", self.t("close_in_15"))),
+                                egui::Button::new(format!("⏱ {}", self.t_minutes("close_in"))),
                             );
 
-                            if button_15.clicked() {
+                            if button_schedule.clicked() {
                                 self.closing_time = Some(
-                                    std::time::Instant::now() + std::time::Duration::from_secs(900),
+                                    std::time::Instant::now()
+                                        + std::time::Duration::from_secs(self.duration_minutes * 60),
                                 );
-                                self.status = self.t("scheduled_close");
+                                self.status = self.t_minutes("scheduled_close");
                                 self.is_scheduled_to_close = true;
                             }
                         } else {
@@ -264,9 +302,14 @@ fn main() -> Result<(), eframe::Error> {
         .cloned()
         .unwrap_or("Child".to_string());
 
-    eframe::run_native(
-        &title,
-        options,
-        Box::new(|_cc| Box::new(DsqChildApp::default())),
-    )
+    let app = DsqChildApp::default();
+    let closed_by_schedule = app.closed_by_schedule.clone();
+
+    eframe::run_native(&title, options, Box::new(|_cc| Box::new(app)))?;
+
+    std::process::exit(if closed_by_schedule.load(Ordering::Relaxed) {
+        EXIT_SCHEDULED
+    } else {
+        EXIT_MANUAL
+    })
 }
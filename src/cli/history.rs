@@ -0,0 +1,115 @@
+use super::usage_error;
+use crate::core::history::{
+    export_history, format_duration, load_history, totals_per_game, totals_per_week,
+    ExportFormat, SessionRecord, Totals,
+};
+use crate::core::presets::write_atomic;
+
+pub fn run(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("stats") => run_stats(&args[1..]),
+        Some("export") => run_export(&args[1..]),
+        Some(other) => usage_error(&format!("Unknown history command: {}", other)),
+        None => usage_error("Missing history command"),
+    }
+}
+
+fn read_history() -> Result<Vec<SessionRecord>, i32> {
+    load_history().map_err(|e| {
+        eprintln!("error: failed to read session history: {}", e);
+        1
+    })
+}
+
+fn run_stats(args: &[String]) -> i32 {
+    if let Some(arg) = args.first() {
+        return usage_error(&format!("Unexpected argument: {}", arg));
+    }
+
+    let records = match read_history() {
+        Ok(records) => records,
+        Err(code) => return code,
+    };
+
+    if records.is_empty() {
+        println!("No sessions recorded yet");
+        return 0;
+    }
+
+    let total: u64 = records.iter().map(|record| record.duration_seconds).sum();
+    println!(
+        "{} sessions, {} simulated\n",
+        records.len(),
+        format_duration(total)
+    );
+    print_totals("Per game", &totals_per_game(&records));
+    println!();
+    print_totals("Per week", &totals_per_week(&records));
+    0
+}
+
+fn print_totals(title: &str, totals: &[Totals]) {
+    println!("{}:", title);
+    let width = totals.iter().map(|t| t.label.chars().count()).max().unwrap_or(0);
+    for t in totals {
+        println!(
+            "  {:<width$}  {:>8}  {} sessions",
+            t.label,
+            format_duration(t.seconds),
+            t.sessions,
+            width = width
+        );
+    }
+}
+
+fn run_export(args: &[String]) -> i32 {
+    let mut format = ExportFormat::Csv;
+    let mut output = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().map(String::as_str) {
+                Some("csv") => format = ExportFormat::Csv,
+                Some("json") => format = ExportFormat::Json,
+                Some(other) => return usage_error(&format!("Unknown export format: {}", other)),
+                None => return usage_error("Missing value for --format"),
+            },
+            "--output" => match args.next() {
+                Some(file) => output = Some(file.as_str()),
+                None => return usage_error("Missing value for --output"),
+            },
+            other => return usage_error(&format!("Unexpected argument: {}", other)),
+        }
+    }
+
+    let records = match read_history() {
+        Ok(records) => records,
+        Err(code) => return code,
+    };
+
+    let data = match export_history(&records, format) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("error: failed to export session history: {}", e);
+            return 1;
+        }
+    };
+
+    match output {
+        Some(file) => match write_atomic(file, data.as_bytes()) {
+            Ok(()) => {
+                println!("Exported {} sessions to {}", records.len(), file);
+                0
+            }
+            Err(e) => {
+                eprintln!("error: failed to write {}: {}", file, e);
+                1
+            }
+        },
+        None => {
+            print!("{}", data);
+            0
+        }
+    }
+}
@@ -0,0 +1,48 @@
+pub mod history;
+pub mod presets;
+pub mod scheduler;
+
+use crate::platform::console::attach_parent_console;
+
+const USAGE: &str = "Usage:
+  DSQProcess                                       Start the graphical interface
+  DSQProcess presets import <file> [--official]    Import a Discord detectable applications file
+  DSQProcess presets lint [file]                   Check a presets file (default: presets.json)
+  DSQProcess presets fmt [file] [--check]          Rewrite a presets file in canonical form
+  DSQProcess presets legacy <file> <output>        Write a copy in the v0 format for old versions
+  DSQProcess history stats                         Show simulated time per game and per week
+  DSQProcess history export [--format csv|json] [--output <file>]
+                                                   Export the session history
+  DSQProcess scheduler list                        List upcoming scheduled simulations
+  DSQProcess scheduler run                         Run scheduled simulations without the interface";
+
+/// Ejecuta un comando de línea si se pasaron argumentos.
+/// Devuelve `None` cuando se debe iniciar la interfaz gráfica.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        return None;
+    }
+    attach_parent_console();
+
+    let code = match args[0].as_str() {
+        "presets" => presets::run(&args[1..]),
+        "history" => history::run(&args[1..]),
+        "scheduler" => scheduler::run(&args[1..]),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            0
+        }
+        other => {
+            eprintln!("Unknown command: {}\n\n{}", other, USAGE);
+            2
+        }
+    };
+    Some(code)
+}
+
+/// Imprime el uso y devuelve el código de error de argumentos inválidos
+pub fn usage_error(message: &str) -> i32 {
+    eprintln!("{}\n\n{}", message, USAGE);
+    2
+}
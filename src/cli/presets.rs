@@ -0,0 +1,199 @@
+use super::usage_error;
+use crate::core::importer::{import_detectable_file, ImportTarget};
+use crate::core::lint::{canonicalize_presets, lint_presets, Severity};
+use crate::core::presets::write_atomic;
+use crate::core::schema::{
+    parse_presets, serialize_legacy_presets, serialize_presets, CURRENT_SCHEMA_VERSION,
+};
+use crate::shared::types::Preset;
+
+const DEFAULT_PRESETS_FILE: &str = "presets.json";
+
+pub fn run(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("import") => run_import(&args[1..]),
+        Some("lint") => run_lint(&args[1..]),
+        Some("fmt") => run_fmt(&args[1..]),
+        Some("legacy") => run_legacy(&args[1..]),
+        Some(other) => usage_error(&format!("Unknown presets command: {}", other)),
+        None => usage_error("Missing presets command"),
+    }
+}
+
+fn run_import(args: &[String]) -> i32 {
+    let mut file = None;
+    let mut target = ImportTarget::Custom;
+
+    for arg in args {
+        match arg.as_str() {
+            "--official" => target = ImportTarget::Official,
+            "--custom" => target = ImportTarget::Custom,
+            other if file.is_none() && !other.starts_with("--") => file = Some(other),
+            other => return usage_error(&format!("Unexpected argument: {}", other)),
+        }
+    }
+
+    let Some(file) = file else {
+        return usage_error("Missing file to import");
+    };
+
+    match import_detectable_file(file, target) {
+        Ok(report) => {
+            println!(
+                "Imported {} presets ({} duplicates, {} without usable executables)",
+                report.added, report.duplicates, report.skipped
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Import failed: {}", e);
+            1
+        }
+    }
+}
+
+/// Lee y parsea un archivo de presets. Las entradas inválidas se informan
+/// con su línea y columna y se devuelven como cantidad de errores.
+fn read_presets_file(file: &str) -> Result<(String, Vec<Preset>, usize), i32> {
+    let data = std::fs::read_to_string(file).map_err(|e| {
+        eprintln!("error: failed to read {}: {}", file, e);
+        1
+    })?;
+
+    let parsed = parse_presets(file, &data).map_err(|e| {
+        eprintln!("error: {}", e);
+        1
+    })?;
+
+    for error in &parsed.errors {
+        eprintln!("error: {}", error);
+    }
+
+    if parsed.schema_version < CURRENT_SCHEMA_VERSION {
+        eprintln!(
+            "warning: {}: uses schema_version {}, run `presets fmt` to migrate to {}",
+            file, parsed.schema_version, CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    Ok((data, parsed.presets, parsed.errors.len()))
+}
+
+/// Imprime los problemas y devuelve la cantidad de errores
+fn report_issues(file: &str, presets: &[Preset]) -> usize {
+    let issues = lint_presets(presets);
+    for issue in &issues {
+        eprintln!("{}: {}", file, issue);
+    }
+    issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count()
+}
+
+fn run_lint(args: &[String]) -> i32 {
+    let file = match args {
+        [] => DEFAULT_PRESETS_FILE,
+        [file] => file.as_str(),
+        _ => return usage_error("Too many arguments for lint"),
+    };
+
+    let (presets, parse_errors) = match read_presets_file(file) {
+        Ok((_, presets, parse_errors)) => (presets, parse_errors),
+        Err(code) => return code,
+    };
+
+    let errors = parse_errors + report_issues(file, &presets);
+    if errors > 0 {
+        eprintln!("{}: {} error(s) in {} presets", file, errors, presets.len());
+        1
+    } else {
+        println!("{}: {} presets OK", file, presets.len());
+        0
+    }
+}
+
+fn run_fmt(args: &[String]) -> i32 {
+    let mut file = None;
+    let mut check = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            other if file.is_none() && !other.starts_with("--") => file = Some(other),
+            other => return usage_error(&format!("Unexpected argument: {}", other)),
+        }
+    }
+    let file = file.unwrap_or(DEFAULT_PRESETS_FILE);
+
+    let (original, presets) = match read_presets_file(file) {
+        Ok((original, presets, 0)) => (original, presets),
+        Ok(_) => {
+            // Reescribir el archivo perdería las entradas que no se pudieron leer
+            eprintln!("{}: fix the invalid entries before formatting", file);
+            return 1;
+        }
+        Err(code) => return code,
+    };
+
+    let canonical = canonicalize_presets(&presets);
+    let formatted = match serialize_presets(&canonical) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("error: failed to serialize presets: {}", e);
+            return 1;
+        }
+    };
+
+    let changed = formatted != original;
+    if check {
+        if changed {
+            eprintln!("{}: not in canonical format", file);
+            return 1;
+        }
+    } else if changed {
+        if let Err(e) = write_atomic(file, formatted.as_bytes()) {
+            eprintln!("error: failed to write {}: {}", file, e);
+            return 1;
+        }
+        println!("{}: formatted {} presets", file, canonical.len());
+    }
+
+    // Los problemas que el formato no corrige (duplicados, rutas inválidas...)
+    if report_issues(file, &canonical) > 0 {
+        return 1;
+    }
+    0
+}
+
+/// Escribe una copia en el formato v0 para las versiones anteriores de la app
+fn run_legacy(args: &[String]) -> i32 {
+    let (file, output) = match args {
+        [file, output] => (file.as_str(), output.as_str()),
+        _ => return usage_error("Expected a presets file and an output file"),
+    };
+
+    let presets = match read_presets_file(file) {
+        Ok((_, presets, 0)) => presets,
+        Ok(_) => {
+            eprintln!("{}: fix the invalid entries before exporting", file);
+            return 1;
+        }
+        Err(code) => return code,
+    };
+
+    let json = match serialize_legacy_presets(&presets) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("error: failed to serialize presets: {}", e);
+            return 1;
+        }
+    };
+
+    if let Err(e) = write_atomic(output, json.as_bytes()) {
+        eprintln!("error: failed to write {}: {}", output, e);
+        return 1;
+    }
+    println!("{}: wrote {} presets in the v0 format", output, presets.len());
+    0
+}
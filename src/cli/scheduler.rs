@@ -0,0 +1,241 @@
+use super::usage_error;
+use crate::core::history::{append_history, ExitReason, SessionRecord};
+use crate::core::presets::load_presets;
+use crate::core::process::{
+    create_fake_process_group, FinishedSession, LaunchOptions, ProcessMonitor,
+};
+use crate::core::queue::SimulationQueue;
+use crate::core::quests::{load_quests, record_session, save_quests};
+use crate::core::scheduler::{
+    merge_last_runs, take_due_runs, upcoming_runs, ScheduledRun, SchedulerLock,
+};
+use crate::shared::config::{load_config, save_config};
+use chrono::Local;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Tiempo máximo para que terminen los procesos al detener el programador
+const STOP_TIMEOUT: Duration = Duration::from_secs(3);
+
+pub fn run(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("list") => run_list(),
+        Some("run") => run_scheduler(),
+        Some(other) => usage_error(&format!("Unknown scheduler command: {}", other)),
+        None => usage_error("Missing scheduler command"),
+    }
+}
+
+fn run_list() -> i32 {
+    let config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+    let upcoming = upcoming_runs(&config.schedules, Local::now());
+
+    if upcoming.is_empty() {
+        println!("No upcoming scheduled simulations");
+        return 0;
+    }
+
+    for (schedule, when) in upcoming {
+        println!(
+            "{}  {} ({} min, {:?}, missed: {:?})",
+            when.format("%Y-%m-%d %H:%M"),
+            schedule.preset,
+            schedule.duration_minutes,
+            schedule.repeat,
+            schedule.missed
+        );
+    }
+    0
+}
+
+/// Ejecuta las simulaciones programadas sin interfaz gráfica
+fn run_scheduler() -> i32 {
+    // Un solo proceso atiende las programaciones: otro `scheduler run`
+    // no arranca y la interfaz las deja en manos de este
+    let _lock = match SchedulerLock::acquire() {
+        Ok(Some(lock)) => lock,
+        Ok(None) => {
+            eprintln!("error: another scheduler is already running");
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("error: failed to lock the scheduler: {}", e);
+            return 1;
+        }
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let handler_stop = Arc::clone(&stop);
+    if let Err(e) = ctrlc::set_handler(move || handler_stop.store(true, Ordering::SeqCst)) {
+        eprintln!("error: failed to install the Ctrl+C handler: {}", e);
+        return 1;
+    }
+
+    let presets = load_presets().presets;
+    let monitor = ProcessMonitor::new();
+    let mut queue = SimulationQueue::default();
+
+    match load_config() {
+        Ok(config) => println!(
+            "Scheduler running with {} scheduled simulation(s), press Ctrl+C to stop",
+            config.schedules.len()
+        ),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    }
+
+    // Programaciones ya atendidas por este proceso, por si config.json
+    // vuelve con un `last_run` viejo
+    let mut attended = Vec::new();
+    let mut config_error = None;
+    while !stop.load(Ordering::SeqCst) {
+        // Releer la configuración para tomar cambios hechos desde la interfaz.
+        // Si no se puede leer, se espera a que la corrijan sin guardar encima.
+        match load_config().and_then(|config| {
+            monitor.set_hooks(config.hooks);
+            take_due_schedules(config.schedules, &mut attended)
+        }) {
+            Ok(due) => {
+                config_error = None;
+                for (preset, duration_minutes) in due {
+                    println!("Scheduled simulation due: {} ({} min)", preset, duration_minutes);
+                    queue.push(&preset, duration_minutes);
+                    queue.running = true;
+                }
+            }
+            Err(e) => {
+                if config_error.as_ref() != Some(&e) {
+                    eprintln!("error: {}; scheduled simulations are paused", e);
+                }
+                config_error = Some(e);
+            }
+        }
+        let finished = monitor.check_and_remove_dead_processes();
+        for session in &finished {
+            println!(
+                "Session ended: {} after {}s ({})",
+                session.preset_name,
+                session.duration().as_secs(),
+                session.exit_reason.as_str()
+            );
+            queue.finish_group(session.group_id);
+        }
+        record_sessions(&finished);
+
+        if let Some(group_id) = queue.overdue_group() {
+            monitor.stop_group(group_id, ExitReason::ScheduledClose);
+        }
+
+        if let Some(item) = queue.next_ready() {
+            match presets
+                .iter()
+                .find(|preset| preset.name.eq_ignore_ascii_case(&item.preset))
+            {
+                Some(preset) => {
+                    let launch = LaunchOptions::from_preset(preset);
+                    match create_fake_process_group(
+                        &preset.host_executables(),
+                        &launch,
+                        item.duration_minutes,
+                    ) {
+                        Ok(spawned) => {
+                            println!("Started {} for {} min", preset.name, item.duration_minutes);
+                            let group_id = monitor.add_process_group(
+                                &preset.name,
+                                item.duration_minutes,
+                                spawned,
+                            );
+                            queue.start(item, group_id);
+                        }
+                        Err(e) => eprintln!("error: failed to start {}: {}", preset.name, e),
+                    }
+                }
+                None => eprintln!("error: preset not found: {}", item.preset),
+            }
+        }
+
+        wait_or_stop(&stop, POLL_INTERVAL);
+    }
+
+    println!("Stopping scheduler");
+    monitor.stop_all(ExitReason::Manual);
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    let mut finished = monitor.check_and_remove_dead_processes();
+    while !monitor.active_sessions().is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(100));
+        finished.extend(monitor.check_and_remove_dead_processes());
+    }
+    finished.extend(monitor.cleanup_all());
+    record_sessions(&finished);
+    0
+}
+
+/// Marca como atendidas las programaciones que llegaron a su hora y
+/// devuelve los presets a iniciar. Vuelve a leer config.json justo antes
+/// de guardar para no pisar cambios hechos mientras tanto.
+fn take_due_schedules(
+    mut schedules: Vec<ScheduledRun>,
+    attended: &mut Vec<ScheduledRun>,
+) -> Result<Vec<(String, u64)>, String> {
+    merge_last_runs(&mut schedules, attended);
+    let due = take_due_runs(&mut schedules, Local::now());
+    attended.clone_from(&schedules);
+    if due.is_empty() {
+        return Ok(due);
+    }
+
+    let mut config = load_config()?;
+    merge_last_runs(&mut config.schedules, &schedules);
+    config.schedules.retain(|schedule| !schedule.is_finished());
+    save_config(&config);
+    Ok(due)
+}
+
+/// Espera `duration` pero vuelve en cuanto se pide detener
+fn wait_or_stop(stop: &AtomicBool, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    while !stop.load(Ordering::SeqCst) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Guarda las sesiones terminadas en el historial y en las misiones
+fn record_sessions(sessions: &[FinishedSession]) {
+    if sessions.is_empty() {
+        return;
+    }
+
+    let records: Vec<SessionRecord> = sessions.iter().map(SessionRecord::from).collect();
+    if let Err(e) = append_history(&records) {
+        eprintln!("error: failed to save session history: {}", e);
+    }
+
+    match load_quests() {
+        Ok(mut quests) => {
+            let mut changed = false;
+            for session in sessions {
+                let progress = record_session(&mut quests, &session.preset_name, session.duration());
+                changed |= progress.changed;
+                for name in progress.completed {
+                    println!("Quest completed: {}", name);
+                }
+            }
+            if changed {
+                if let Err(e) = save_quests(&quests) {
+                    eprintln!("error: failed to save quests: {}", e);
+                }
+            }
+        }
+        Err(e) => eprintln!("error: failed to read quests: {}", e),
+    }
+}
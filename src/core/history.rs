@@ -0,0 +1,313 @@
+use crate::core::presets::write_atomic;
+use crate::core::process::FinishedSession;
+use chrono::{DateTime, Datelike, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const HISTORY_FILE: &str = "session_history.json";
+
+/// Códigos de salida de DSQChild (deben coincidir con src/child/main.rs)
+pub const CHILD_EXIT_SCHEDULED: i32 = 0;
+pub const CHILD_EXIT_MANUAL: i32 = 10;
+
+/// Motivo por el que terminó una sesión simulada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    ScheduledClose,
+    Manual,
+    Crash,
+    ParentShutdown,
+}
+
+impl ExitReason {
+    pub fn from_exit_code(code: Option<i32>) -> Self {
+        match code {
+            Some(CHILD_EXIT_SCHEDULED) => ExitReason::ScheduledClose,
+            Some(CHILD_EXIT_MANUAL) => ExitReason::Manual,
+            _ => ExitReason::Crash,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExitReason::ScheduledClose => "scheduled_close",
+            ExitReason::Manual => "manual",
+            ExitReason::Crash => "crash",
+            ExitReason::ParentShutdown => "parent_shutdown",
+        }
+    }
+
+    pub fn lang_key(&self) -> &'static str {
+        match self {
+            ExitReason::ScheduledClose => "exit_scheduled_close",
+            ExitReason::Manual => "exit_manual",
+            ExitReason::Crash => "exit_crash",
+            ExitReason::ParentShutdown => "exit_parent_shutdown",
+        }
+    }
+}
+
+/// Sesión guardada en el historial
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub preset: String,
+    pub executables: Vec<String>,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    pub duration_seconds: u64,
+    pub exit_reason: ExitReason,
+}
+
+impl From<&FinishedSession> for SessionRecord {
+    fn from(session: &FinishedSession) -> Self {
+        Self {
+            preset: session.preset_name.clone(),
+            executables: session.executables.clone(),
+            started_at: session.started_at,
+            ended_at: session.ended_at,
+            duration_seconds: session.duration().as_secs(),
+            exit_reason: session.exit_reason,
+        }
+    }
+}
+
+/// Carga el historial de sesiones (vacío si el archivo no existe)
+pub fn load_history() -> Result<Vec<SessionRecord>, Box<dyn std::error::Error>> {
+    if !std::path::Path::new(HISTORY_FILE).exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(HISTORY_FILE)?;
+    let records = serde_json::from_str(&data).map_err(|e| format!("{}: {}", HISTORY_FILE, e))?;
+    Ok(records)
+}
+
+/// Agrega sesiones al historial guardado
+pub fn append_history(records: &[SessionRecord]) -> Result<(), Box<dyn std::error::Error>> {
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    let mut history = load_history()?;
+    history.extend_from_slice(records);
+
+    let json = serde_json::to_string_pretty(&history)?;
+    write_atomic(HISTORY_FILE, json.as_bytes())?;
+    log::info!("Saved {} session(s) to history", records.len());
+    Ok(())
+}
+
+/// Tiempo total simulado de un grupo de sesiones
+#[derive(Debug, Clone)]
+pub struct Totals {
+    pub label: String,
+    pub sessions: usize,
+    pub seconds: u64,
+}
+
+/// Totales por juego, de mayor a menor tiempo
+pub fn totals_per_game(records: &[SessionRecord]) -> Vec<Totals> {
+    let mut totals = group_totals(records, |record| record.preset.clone());
+    totals.sort_by(|a, b| b.seconds.cmp(&a.seconds).then(a.label.cmp(&b.label)));
+    totals
+}
+
+/// Totales por semana ISO (p. ej. `2026-W42`), la más reciente primero
+pub fn totals_per_week(records: &[SessionRecord]) -> Vec<Totals> {
+    let mut totals = group_totals(records, |record| {
+        let week = record.started_at.iso_week();
+        format!("{}-W{:02}", week.year(), week.week())
+    });
+    totals.reverse();
+    totals
+}
+
+fn group_totals(records: &[SessionRecord], key: impl Fn(&SessionRecord) -> String) -> Vec<Totals> {
+    let mut groups: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+    for record in records {
+        let entry = groups.entry(key(record)).or_default();
+        entry.0 += 1;
+        entry.1 += record.duration_seconds;
+    }
+
+    groups
+        .into_iter()
+        .map(|(label, (sessions, seconds))| Totals {
+            label,
+            sessions,
+            seconds,
+        })
+        .collect()
+}
+
+/// Formatea segundos como `1h 05m` o `12m`
+pub fn format_duration(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Serializa el historial en el formato pedido
+pub fn export_history(
+    records: &[SessionRecord],
+    format: ExportFormat,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match format {
+        ExportFormat::Json => {
+            let mut json = serde_json::to_string_pretty(records)?;
+            json.push('\n');
+            Ok(json)
+        }
+        ExportFormat::Csv => Ok(to_csv(records)),
+    }
+}
+
+fn to_csv(records: &[SessionRecord]) -> String {
+    let mut csv =
+        String::from("preset,executables,started_at,ended_at,duration_seconds,exit_reason\n");
+    for record in records {
+        let fields = [
+            csv_field(&record.preset),
+            csv_field(&record.executables.join(";")),
+            record.started_at.to_rfc3339(),
+            record.ended_at.to_rfc3339(),
+            record.duration_seconds.to_string(),
+            record.exit_reason.as_str().to_string(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Entrecomilla un campo CSV si hace falta
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(preset: &str, (year, month, day): (i32, u32, u32), minutes: u64) -> SessionRecord {
+        let started_at = Local.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap();
+        SessionRecord {
+            preset: preset.to_string(),
+            executables: vec!["game.exe".to_string()],
+            started_at,
+            ended_at: started_at + chrono::Duration::minutes(minutes as i64),
+            duration_seconds: minutes * 60,
+            exit_reason: ExitReason::ScheduledClose,
+        }
+    }
+
+    #[test]
+    fn exit_codes_map_to_reasons() {
+        assert_eq!(
+            ExitReason::from_exit_code(Some(CHILD_EXIT_SCHEDULED)),
+            ExitReason::ScheduledClose
+        );
+        assert_eq!(ExitReason::from_exit_code(Some(CHILD_EXIT_MANUAL)), ExitReason::Manual);
+        assert_eq!(ExitReason::from_exit_code(Some(1)), ExitReason::Crash);
+        assert_eq!(ExitReason::from_exit_code(None), ExitReason::Crash);
+    }
+
+    #[test]
+    fn durations_are_formatted() {
+        assert_eq!(format_duration(0), "0m");
+        assert_eq!(format_duration(59 * 60 + 59), "59m");
+        assert_eq!(format_duration(3600), "1h 00m");
+        assert_eq!(format_duration(3900), "1h 05m");
+        assert_eq!(format_duration(25 * 3600 + 30 * 60), "25h 30m");
+    }
+
+    #[test]
+    fn games_are_sorted_by_time() {
+        let records = [
+            record("Beta", (2026, 3, 2), 15),
+            record("Alpha", (2026, 3, 3), 15),
+            record("Gamma", (2026, 3, 4), 10),
+            record("Gamma", (2026, 3, 5), 10),
+        ];
+
+        let totals = totals_per_game(&records);
+
+        let labels: Vec<_> = totals.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(labels, ["Gamma", "Alpha", "Beta"]);
+        assert_eq!(totals[0].sessions, 2);
+        assert_eq!(totals[0].seconds, 20 * 60);
+    }
+
+    #[test]
+    fn weeks_follow_iso_years() {
+        // El lunes 29 de diciembre de 2025 ya pertenece a la semana 1 de 2026
+        let records = [
+            record("Quest", (2025, 12, 28), 10),
+            record("Quest", (2025, 12, 29), 20),
+            record("Quest", (2026, 1, 1), 30),
+            record("Quest", (2026, 1, 5), 40),
+        ];
+
+        let totals = totals_per_week(&records);
+
+        let weeks: Vec<_> = totals
+            .iter()
+            .map(|t| (t.label.as_str(), t.sessions, t.seconds / 60))
+            .collect();
+        assert_eq!(
+            weeks,
+            [("2026-W02", 1, 40), ("2026-W01", 2, 50), ("2025-W52", 1, 10)]
+        );
+    }
+
+    #[test]
+    fn csv_quotes_commas_and_quotes() {
+        let mut quoted = record("Foo, \"Bar\"", (2026, 3, 2), 15);
+        quoted.executables.push("launcher.exe".to_string());
+        quoted.exit_reason = ExitReason::Manual;
+        let records = [quoted, record("Plain", (2026, 3, 3), 5)];
+
+        let csv = export_history(&records, ExportFormat::Csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "preset,executables,started_at,ended_at,duration_seconds,exit_reason"
+        );
+        assert!(lines[1].starts_with("\"Foo, \"\"Bar\"\"\",game.exe;launcher.exe,"));
+        assert!(lines[1].ends_with(",900,manual"));
+        assert!(lines[2].starts_with("Plain,game.exe,"));
+        assert!(lines[2].ends_with(",300,scheduled_close"));
+    }
+
+    #[test]
+    fn csv_fields_only_quoted_when_needed() {
+        assert_eq!(csv_field("Quest"), "Quest");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,329 @@
+use crate::core::history::ExitReason;
+use crate::core::process::FinishedSession;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::process::{Command, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Tiempo máximo por defecto de cada hook, en segundos
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 10;
+
+fn default_hook_timeout() -> u64 {
+    DEFAULT_HOOK_TIMEOUT_SECONDS
+}
+
+/// Momento de la sesión en el que se ejecuta un hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    Start,
+    /// La sesión terminó de forma normal (programada, manual o al cerrar la app)
+    End,
+    /// El proceso simulado terminó de forma inesperada
+    Failure,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::Start => "start",
+            HookEvent::End => "end",
+            HookEvent::Failure => "failure",
+        }
+    }
+}
+
+/// Hook configurado en config.json. Puede enviar un POST a `url`,
+/// ejecutar `command`, o ambas cosas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionHook {
+    pub events: Vec<HookEvent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Línea de comandos que se ejecuta con la shell del sistema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_seconds: u64,
+}
+
+/// Datos de la sesión que recibe cada hook
+#[derive(Debug, Clone, Serialize)]
+pub struct HookPayload {
+    pub event: HookEvent,
+    pub session_id: u64,
+    pub preset: String,
+    pub executables: Vec<String>,
+    pub started_at: DateTime<Local>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Local>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_reason: Option<&'static str>,
+}
+
+impl HookPayload {
+    pub fn started(
+        session_id: u64,
+        preset: &str,
+        executables: Vec<String>,
+        started_at: DateTime<Local>,
+    ) -> Self {
+        Self {
+            event: HookEvent::Start,
+            session_id,
+            preset: preset.to_string(),
+            executables,
+            started_at,
+            ended_at: None,
+            duration_seconds: None,
+            exit_reason: None,
+        }
+    }
+
+    /// Variables de entorno para los hooks de tipo comando
+    fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("DSQ_EVENT", self.event.as_str().to_string()),
+            ("DSQ_SESSION_ID", self.session_id.to_string()),
+            ("DSQ_PRESET", self.preset.clone()),
+            ("DSQ_EXECUTABLES", self.executables.join(",")),
+            ("DSQ_STARTED_AT", self.started_at.to_rfc3339()),
+        ];
+        if let Some(ended_at) = self.ended_at {
+            vars.push(("DSQ_ENDED_AT", ended_at.to_rfc3339()));
+        }
+        if let Some(duration) = self.duration_seconds {
+            vars.push(("DSQ_DURATION_SECONDS", duration.to_string()));
+        }
+        if let Some(reason) = self.exit_reason {
+            vars.push(("DSQ_EXIT_REASON", reason.to_string()));
+        }
+        vars
+    }
+}
+
+impl From<&FinishedSession> for HookPayload {
+    fn from(session: &FinishedSession) -> Self {
+        let event = match session.exit_reason {
+            ExitReason::Crash => HookEvent::Failure,
+            _ => HookEvent::End,
+        };
+        Self {
+            event,
+            session_id: session.group_id,
+            preset: session.preset_name.clone(),
+            executables: session.executables.clone(),
+            started_at: session.started_at,
+            ended_at: Some(session.ended_at),
+            duration_seconds: Some(session.duration().as_secs()),
+            exit_reason: Some(session.exit_reason.as_str()),
+        }
+    }
+}
+
+/// Ejecuta en segundo plano los hooks suscritos al evento del payload.
+/// Devuelve el hilo, o `None` si ningún hook aplica.
+pub fn fire_hooks(hooks: &[SessionHook], payload: HookPayload) -> Option<JoinHandle<()>> {
+    let hooks: Vec<SessionHook> = hooks
+        .iter()
+        .filter(|hook| hook.events.contains(&payload.event))
+        .cloned()
+        .collect();
+    if hooks.is_empty() {
+        return None;
+    }
+
+    Some(std::thread::spawn(move || {
+        for hook in &hooks {
+            run_hook(hook, &payload);
+        }
+    }))
+}
+
+fn run_hook(hook: &SessionHook, payload: &HookPayload) {
+    let timeout = Duration::from_secs(hook.timeout_seconds.max(1));
+
+    if let Some(url) = &hook.url {
+        match post_webhook(url, payload, timeout) {
+            Ok(()) => log::info!(
+                "Webhook {} notified of {} for session {}",
+                url,
+                payload.event.as_str(),
+                payload.session_id
+            ),
+            Err(e) => log::warn!("Webhook {} failed: {}", url, e),
+        }
+    }
+
+    if let Some(command) = &hook.command {
+        match run_command(command, payload, timeout) {
+            Ok(()) => log::info!(
+                "Hook command ran for {} of session {}",
+                payload.event.as_str(),
+                payload.session_id
+            ),
+            Err(e) => log::warn!("Hook command `{}` failed: {}", command, e),
+        }
+    }
+}
+
+fn post_webhook(
+    url: &str,
+    payload: &HookPayload,
+    timeout: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .user_agent(format!("DSQProcess/{}", crate::platform::update::VERSION))
+        .build()?;
+    let response = client.post(url).json(payload).send()?;
+    if !response.status().is_success() {
+        return Err(format!("server responded with {}", response.status()).into());
+    }
+    Ok(())
+}
+
+fn run_command(
+    command: &str,
+    payload: &HookPayload,
+    timeout: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut process = if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+
+    let mut child = process
+        .envs(payload.env_vars())
+        .stdin(Stdio::null())
+        .spawn()?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if !status.success() {
+                return Err(format!("exited with {}", status).into());
+            }
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("timed out after {}s", timeout.as_secs()).into());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    fn payload() -> HookPayload {
+        HookPayload::started(7, "Valorant", vec!["VALORANT.exe".to_string()], Local::now())
+    }
+
+    /// Servidor de un solo pedido en un puerto libre: responde `status`
+    /// y envía el cuerpo recibido por el canal
+    fn webhook_server(status: u16) -> (String, mpsc::Receiver<serde_json::Value>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let _ = sender.send(serde_json::from_slice(&body).unwrap());
+
+            let reply = format!(
+                "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            reader.into_inner().write_all(reply.as_bytes()).unwrap();
+        });
+
+        (url, receiver)
+    }
+
+    #[test]
+    fn webhook_posts_payload() {
+        let (url, received) = webhook_server(204);
+
+        post_webhook(&url, &payload(), Duration::from_secs(5)).unwrap();
+
+        let body = received.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(body["event"], "start");
+        assert_eq!(body["session_id"], 7);
+        assert_eq!(body["preset"], "Valorant");
+        assert_eq!(body["executables"][0], "VALORANT.exe");
+        assert!(body.get("ended_at").is_none());
+    }
+
+    #[test]
+    fn webhook_reports_error_status() {
+        let (url, _received) = webhook_server(401);
+
+        let error = post_webhook(&url, &payload(), Duration::from_secs(5)).unwrap_err();
+        assert!(error.to_string().contains("401"));
+    }
+
+    #[test]
+    fn hooks_only_fire_for_their_events() {
+        let hook = SessionHook {
+            events: vec![HookEvent::End, HookEvent::Failure],
+            url: Some("http://127.0.0.1:9/unused".to_string()),
+            command: None,
+            timeout_seconds: 1,
+        };
+        assert!(fire_hooks(&[hook], payload()).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_receives_session_environment() {
+        let output = std::env::temp_dir().join(format!("dsq-hook-{}.txt", std::process::id()));
+        let command = format!(
+            "echo \"$DSQ_EVENT $DSQ_SESSION_ID $DSQ_PRESET\" > {}",
+            output.display()
+        );
+
+        run_command(&command, &payload(), Duration::from_secs(5)).unwrap();
+
+        let written = std::fs::read_to_string(&output).unwrap();
+        let _ = std::fs::remove_file(&output);
+        assert_eq!(written.trim(), "start 7 Valorant");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_times_out() {
+        let error = run_command("sleep 5", &payload(), Duration::from_millis(200)).unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::core::presets::{
+    load_custom_presets, load_official_presets, save_custom_presets, save_official_presets,
+};
+use crate::shared::types::{ExecutableRole, ExecutableVariant, Preset, PresetExecutable};
+use serde::Deserialize;
+use std::fs;
+
+/// Entrada del formato "detectable applications" de Discord
+#[derive(Deserialize, Debug)]
+pub struct DetectableApp {
+    pub name: String,
+    #[serde(default)]
+    pub executables: Vec<DetectableExecutable>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DetectableExecutable {
+    pub os: String,
+    pub name: String,
+    #[serde(default)]
+    pub is_launcher: bool,
+}
+
+/// Archivo de presets donde se fusiona la importación
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportTarget {
+    Custom,
+    Official,
+}
+
+/// Resultado de una importación
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub added: usize,
+    pub duplicates: usize,
+    pub skipped: usize,
+}
+
+/// Lee un archivo local con el formato de Discord
+pub fn read_detectable_file(path: &str) -> Result<Vec<DetectableApp>, Box<dyn std::error::Error>> {
+    let data = fs::read_to_string(path)?;
+    let apps = serde_json::from_str(&data)
+        .map_err(|e| format!("Invalid detectable applications file: {}", e))?;
+    Ok(apps)
+}
+
+/// Convierte una aplicación de Discord en un preset. Cada ejecutable de
+/// Windows se agrega a la lista; el primero es el juego y el resto alternativos.
+/// Los ejecutables de Linux y macOS se asignan en orden como variantes.
+pub fn convert_detectable_app(app: &DetectableApp) -> Option<Preset> {
+    let name = app.name.trim();
+
+    let mut executables: Vec<PresetExecutable> = executables_for_os(app, "win32")
+        .into_iter()
+        .enumerate()
+        .map(|(index, (path, executable))| PresetExecutable {
+            path: if path.is_empty() {
+                folder_from_name(name)
+            } else {
+                path
+            },
+            executable,
+            role: if index == 0 {
+                ExecutableRole::Game
+            } else {
+                ExecutableRole::Alternate
+            },
+            ..Default::default()
+        })
+        .collect();
+
+    if executables.is_empty() {
+        return None;
+    }
+
+    for (exe, (path, executable)) in executables.iter_mut().zip(executables_for_os(app, "linux")) {
+        exe.linux = Some(variant_from(path, executable));
+    }
+    for (exe, (path, executable)) in executables.iter_mut().zip(executables_for_os(app, "darwin")) {
+        exe.macos = Some(variant_from(path, executable));
+    }
+
+    Some(Preset {
+        name: name.to_string(),
+        executables,
+        ..Default::default()
+    })
+}
+
+/// Ejecutables de un sistema (sin launchers), separados en (carpeta, nombre)
+fn executables_for_os(app: &DetectableApp, os: &str) -> Vec<(String, String)> {
+    app.executables
+        .iter()
+        .filter(|exe| exe.os == os && !exe.is_launcher)
+        .filter_map(|exe| split_executable(&exe.name))
+        .collect()
+}
+
+fn variant_from(path: String, executable: String) -> ExecutableVariant {
+    ExecutableVariant {
+        executable,
+        path: (!path.is_empty()).then_some(path),
+    }
+}
+
+/// Separa "carpeta/sub/juego.exe" en ("carpeta/sub", "juego.exe")
+fn split_executable(raw: &str) -> Option<(String, String)> {
+    // Discord marca con '>' los nombres que deben coincidir exactamente
+    let raw = raw.trim().trim_start_matches('>').replace('\\', "/");
+    let raw = raw.trim_matches('/');
+
+    match raw.rsplit_once('/') {
+        Some((path, exe)) if !exe.is_empty() => Some((path.to_string(), exe.to_string())),
+        Some(_) => None,
+        None if !raw.is_empty() => Some((String::new(), raw.to_string())),
+        None => None,
+    }
+}
+
+/// Genera un nombre de carpeta válido a partir del nombre del juego
+fn folder_from_name(name: &str) -> String {
+    let folder: String = name
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
+        .collect();
+    folder.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_duplicate(existing: &[Preset], preset: &Preset, aliases: &[String]) -> bool {
+    existing.iter().any(|p| {
+        p.name.eq_ignore_ascii_case(&preset.name)
+            || aliases.iter().any(|a| p.name.eq_ignore_ascii_case(a))
+            || preset
+                .executables
+                .iter()
+                .any(|exe| p.matches(&exe.executable, &exe.path))
+    })
+}
+
+/// Fusiona las aplicaciones en la lista de presets, omitiendo duplicados
+/// tanto de la propia lista como de `others` (el otro archivo de presets)
+pub fn merge_detectable_apps(
+    existing: &mut Vec<Preset>,
+    others: &[Preset],
+    apps: &[DetectableApp],
+) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for app in apps {
+        let Some(preset) = convert_detectable_app(app) else {
+            report.skipped += 1;
+            continue;
+        };
+
+        if is_duplicate(existing, &preset, &app.aliases)
+            || is_duplicate(others, &preset, &app.aliases)
+        {
+            report.duplicates += 1;
+        } else {
+            existing.push(preset);
+            report.added += 1;
+        }
+    }
+
+    report
+}
+
+/// Importa un archivo de Discord en los presets personalizados u oficiales
+pub fn import_detectable_file(
+    path: &str,
+    target: ImportTarget,
+) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    let apps = read_detectable_file(path)?;
+
+    let report = match target {
+        ImportTarget::Custom => {
+            let official = load_official_presets().unwrap_or_default();
+            // Si el archivo tiene entradas inválidas no se toca: guardarlo
+            // las borraría
+            let mut presets = load_custom_presets()?;
+            let report = merge_detectable_apps(&mut presets, &official, &apps);
+            for preset in &mut presets {
+                preset.is_custom = true;
+            }
+            save_custom_presets(&presets)?;
+            report
+        }
+        ImportTarget::Official => {
+            let custom = load_custom_presets().unwrap_or_default();
+            let mut presets = load_official_presets()?;
+            let report = merge_detectable_apps(&mut presets, &custom, &apps);
+            save_official_presets(&presets)?;
+            report
+        }
+    };
+
+    log::info!(
+        "Imported {} presets from {} ({} duplicates, {} skipped)",
+        report.added,
+        path,
+        report.duplicates,
+        report.skipped
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::in_temp_dir;
+
+    const DETECTABLE: &str = r#"[
+        {"name": "New Game", "executables": [{"os": "win32", "name": "newgame/game.exe"}]}
+    ]"#;
+
+    #[test]
+    fn import_keeps_custom_file_with_invalid_entry() {
+        in_temp_dir(|dir| {
+            let custom = r#"{
+  "schema_version": 2,
+  "presets": [
+    {"name": "Mine", "executables": [{"executable": "mine.exe", "path": "Mine"}]},
+    {"name": "Broken", "executables": [{"executable": "b.exe", "path": "B", "role": "boss"}]}
+  ]
+}"#;
+            std::fs::write("presets_custom.json", custom).unwrap();
+            std::fs::write("detectable.json", DETECTABLE).unwrap();
+
+            let result = import_detectable_file("detectable.json", ImportTarget::Custom);
+
+            assert!(result.is_err());
+            let after = std::fs::read_to_string(dir.join("presets_custom.json")).unwrap();
+            assert_eq!(after, custom);
+        });
+    }
+
+    #[test]
+    fn import_adds_to_custom_file() {
+        in_temp_dir(|_| {
+            std::fs::write("detectable.json", DETECTABLE).unwrap();
+
+            let report = import_detectable_file("detectable.json", ImportTarget::Custom).unwrap();
+
+            assert_eq!(report.added, 1);
+            let presets = load_custom_presets().unwrap();
+            assert_eq!(presets.len(), 1);
+            assert_eq!(presets[0].name, "New Game");
+        });
+    }
+}
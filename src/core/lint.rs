@@ -0,0 +1,459 @@
+use crate::core::process::TEMPLATE_PLACEHOLDERS;
+use crate::shared::types::{ExecutableVariant, Preset, PresetExecutable};
+use std::collections::HashMap;
+use std::fmt;
+
+const INVALID_EXE_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Problema encontrado en un archivo de presets
+#[derive(Debug)]
+pub struct LintIssue {
+    pub severity: Severity,
+    pub index: usize,
+    pub name: String,
+    pub message: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}: [{}] {}: {}", level, self.index, self.name, self.message)
+    }
+}
+
+/// Clave de orden canónico de los presets
+fn sort_key(preset: &Preset) -> String {
+    preset.name.trim().to_lowercase()
+}
+
+/// Revisa una lista de presets y devuelve los problemas encontrados
+pub fn lint_presets(presets: &[Preset]) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let mut names: HashMap<String, usize> = HashMap::new();
+    let mut targets: HashMap<String, usize> = HashMap::new();
+    let mut executables: HashMap<String, usize> = HashMap::new();
+
+    for (index, preset) in presets.iter().enumerate() {
+        let mut push = |severity, message: String| {
+            issues.push(LintIssue {
+                severity,
+                index,
+                name: preset.name.clone(),
+                message,
+            })
+        };
+
+        if preset.name.trim().is_empty() {
+            push(Severity::Error, "empty name".into());
+        }
+        if preset.name != preset.name.trim() {
+            push(Severity::Warning, "leading or trailing whitespace in name".into());
+        }
+        if preset.executables.is_empty() {
+            push(Severity::Error, "no executables".into());
+        }
+
+        if preset.category.as_deref().is_some_and(|c| c.trim().is_empty()) {
+            push(Severity::Warning, "empty category".into());
+        }
+        if canonical_tags(&preset.tags) != preset.tags {
+            push(Severity::Warning, "empty, duplicate or untrimmed tags".into());
+        }
+
+        if preset.duration_minutes == Some(0) {
+            push(Severity::Error, "duration_minutes must be greater than 0".into());
+        }
+        if let Some(problem) = preset.cwd.as_deref().and_then(check_path) {
+            push(Severity::Error, format!("cwd: {}", problem));
+        }
+        for key in preset.env.keys() {
+            if key.trim().is_empty() || key.contains('=') {
+                push(Severity::Error, format!("invalid environment variable name {:?}", key));
+            }
+        }
+        let templates = preset
+            .args
+            .iter()
+            .chain(preset.cwd.iter())
+            .chain(preset.env.values());
+        for template in templates {
+            for placeholder in unknown_placeholders(template) {
+                push(
+                    Severity::Warning,
+                    format!("unknown placeholder {} in {:?}", placeholder, template),
+                );
+            }
+        }
+
+        let name_key = sort_key(preset);
+        if let Some(first) = names.insert(name_key, index) {
+            push(Severity::Error, format!("duplicate name (first at [{}])", first));
+        }
+
+        for (exe_index, exe) in preset.executables.iter().enumerate() {
+            let prefix = format!("executables[{}]: ", exe_index);
+
+            if exe.executable.trim().is_empty() {
+                push(Severity::Error, format!("{}empty executable", prefix));
+            }
+            if exe.path.trim().is_empty() {
+                push(Severity::Error, format!("{}empty path", prefix));
+            }
+            if exe.executable != exe.executable.trim() || exe.path != exe.path.trim() {
+                push(
+                    Severity::Warning,
+                    format!("{}leading or trailing whitespace", prefix),
+                );
+            }
+
+            if let Some(problem) = check_executable(&exe.executable) {
+                push(Severity::Error, format!("{}{}", prefix, problem));
+            }
+
+            if let Some(problem) = check_path(&exe.path) {
+                push(Severity::Error, format!("{}{}", prefix, problem));
+            }
+
+            for (os, variant) in [("linux", &exe.linux), ("macos", &exe.macos)] {
+                let Some(variant) = variant else {
+                    continue;
+                };
+                let prefix = format!("executables[{}].{}: ", exe_index, os);
+
+                if variant.executable.trim().is_empty() {
+                    push(Severity::Error, format!("{}empty executable", prefix));
+                }
+                if let Some(problem) = check_executable(&variant.executable) {
+                    push(Severity::Error, format!("{}{}", prefix, problem));
+                }
+                if let Some(problem) = variant.path.as_deref().and_then(check_path) {
+                    push(Severity::Error, format!("{}{}", prefix, problem));
+                }
+            }
+
+            let target_key = format!(
+                "{}/{}",
+                exe.path.trim().replace('\\', "/").to_lowercase(),
+                exe.executable.trim().to_lowercase()
+            );
+            if let Some(first) = targets.insert(target_key, index) {
+                push(
+                    Severity::Error,
+                    format!("{}duplicate executable and path (first at [{}])", prefix, first),
+                );
+            } else if let Some(first) =
+                executables.insert(exe.executable.trim().to_lowercase(), index)
+            {
+                if first != index {
+                    push(
+                        Severity::Warning,
+                        format!("{}executable also used at [{}]", prefix, first),
+                    );
+                }
+            }
+        }
+    }
+
+    for (index, pair) in presets.windows(2).enumerate() {
+        if sort_key(&pair[0]) > sort_key(&pair[1]) {
+            issues.push(LintIssue {
+                severity: Severity::Error,
+                index: index + 1,
+                name: pair[1].name.clone(),
+                message: format!("not sorted (should come before {:?})", pair[0].name),
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.index);
+    issues
+}
+
+/// Valida que el nombre del ejecutable no tenga separadores ni caracteres inválidos
+pub fn check_executable(executable: &str) -> Option<String> {
+    let c = executable
+        .chars()
+        .find(|c| INVALID_EXE_CHARS.contains(c) || c.is_control())?;
+
+    if c == '/' || c == '\\' {
+        Some("executable contains a path separator".into())
+    } else {
+        Some(format!("executable contains invalid character {:?}", c))
+    }
+}
+
+/// Variables `{...}` que no son plantillas conocidas
+fn unknown_placeholders(template: &str) -> Vec<&str> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start..start + len + 1];
+        if !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            unknown.push(placeholder);
+        }
+        rest = &rest[start + len + 1..];
+    }
+    unknown
+}
+
+/// Valida que la ruta sea relativa y no salga de la carpeta Games
+pub fn check_path(path: &str) -> Option<String> {
+    let normalized = path.trim().replace('\\', "/");
+    let bytes = normalized.as_bytes();
+
+    if normalized.starts_with('/')
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+    {
+        return Some("path must be relative".into());
+    }
+
+    if normalized.split('/').any(|component| component == "..") {
+        return Some("path must not contain '..'".into());
+    }
+
+    None
+}
+
+/// Devuelve los presets en forma canónica: campos recortados,
+/// separadores '/' y ordenados por nombre
+pub fn canonicalize_presets(presets: &[Preset]) -> Vec<Preset> {
+    let mut canonical: Vec<Preset> = presets
+        .iter()
+        .map(|preset| Preset {
+            name: preset.name.trim().to_string(),
+            executables: preset
+                .executables
+                .iter()
+                .map(|exe| PresetExecutable {
+                    executable: exe.executable.trim().to_string(),
+                    path: canonical_path(&exe.path),
+                    role: exe.role,
+                    linux: exe.linux.as_ref().map(canonical_variant),
+                    macos: exe.macos.as_ref().map(canonical_variant),
+                })
+                .collect(),
+            category: preset
+                .category
+                .as_deref()
+                .map(str::trim)
+                .filter(|category| !category.is_empty())
+                .map(str::to_string),
+            tags: canonical_tags(&preset.tags),
+            args: preset.args.clone(),
+            duration_minutes: preset.duration_minutes,
+            cwd: preset
+                .cwd
+                .as_deref()
+                .map(canonical_path)
+                .filter(|cwd| !cwd.is_empty()),
+            env: preset
+                .env
+                .iter()
+                .map(|(key, value)| (key.trim().to_string(), value.clone()))
+                .collect(),
+            is_custom: preset.is_custom,
+        })
+        .collect();
+
+    canonical.sort_by_key(sort_key);
+    canonical
+}
+
+fn canonical_path(path: &str) -> String {
+    path.trim()
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn canonical_variant(variant: &ExecutableVariant) -> ExecutableVariant {
+    ExecutableVariant {
+        executable: variant.executable.trim().to_string(),
+        path: variant
+            .path
+            .as_deref()
+            .map(canonical_path)
+            .filter(|path| !path.is_empty()),
+    }
+}
+
+/// Etiquetas recortadas, sin vacías ni duplicadas, en el orden original
+pub fn canonical_tags(tags: &[String]) -> Vec<String> {
+    let mut canonical: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !canonical.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            canonical.push(tag.to_string());
+        }
+    }
+    canonical
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::serialize_presets;
+
+    fn preset(name: &str, executable: &str, path: &str) -> Preset {
+        Preset {
+            name: name.to_string(),
+            executables: vec![PresetExecutable {
+                executable: executable.to_string(),
+                path: path.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Mensajes de los problemas, con su gravedad y posición
+    fn issues(presets: &[Preset]) -> Vec<(Severity, usize, String)> {
+        lint_presets(presets)
+            .into_iter()
+            .map(|issue| (issue.severity, issue.index, issue.message))
+            .collect()
+    }
+
+    #[test]
+    fn valid_presets_have_no_issues() {
+        let presets = [
+            preset("Apex Legends", "r5apex.exe", "Apex Legends"),
+            preset("Valorant", "VALORANT.exe", "Riot Games/VALORANT/live"),
+        ];
+        assert!(lint_presets(&presets).is_empty());
+    }
+
+    #[test]
+    fn duplicate_names_and_executables() {
+        let presets = [
+            preset("Game", "game.exe", "Game"),
+            preset("game ", "game.exe", "Game"),
+            preset("Other", "game.exe", "Other"),
+        ];
+        let issues = issues(&presets);
+
+        assert!(issues.contains(&(Severity::Error, 1, "duplicate name (first at [0])".into())));
+        assert!(issues.contains(&(
+            Severity::Error,
+            1,
+            "executables[0]: duplicate executable and path (first at [0])".into()
+        )));
+        assert!(issues.contains(&(
+            Severity::Warning,
+            2,
+            "executables[0]: executable also used at [0]".into()
+        )));
+        assert!(issues
+            .iter()
+            .all(|(_, index, message)| *index != 1 || !message.contains("also used")));
+
+        // La misma ruta con otros separadores también es un duplicado
+        let presets = [preset("A", "a.exe", "Games\\A"), preset("B", "A.EXE", "games/a")];
+        assert!(lint_presets(&presets).iter().any(|issue| {
+            issue.severity == Severity::Error
+                && issue.message == "executables[0]: duplicate executable and path (first at [0])"
+        }));
+    }
+
+    #[test]
+    fn executable_names_are_checked() {
+        assert_eq!(check_executable("game.exe"), None);
+        assert_eq!(
+            check_executable("bin/game.exe").as_deref(),
+            Some("executable contains a path separator")
+        );
+        assert_eq!(
+            check_executable("bin\\game.exe").as_deref(),
+            Some("executable contains a path separator")
+        );
+        assert_eq!(
+            check_executable("game?.exe").as_deref(),
+            Some("executable contains invalid character '?'")
+        );
+        assert_eq!(
+            check_executable("game\u{7}.exe").as_deref(),
+            Some("executable contains invalid character '\\u{7}'")
+        );
+
+        let issues = issues(&[preset("Game", "a|b.exe", "Game")]);
+        assert_eq!(
+            issues,
+            vec![(
+                Severity::Error,
+                0,
+                "executables[0]: executable contains invalid character '|'".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn paths_must_stay_inside_games() {
+        assert_eq!(check_path("Riot Games/VALORANT"), None);
+        assert_eq!(check_path("Game/..data"), None);
+        for path in ["..", "Game/../..", "Game\\..\\Other"] {
+            assert_eq!(check_path(path).as_deref(), Some("path must not contain '..'"), "{}", path);
+        }
+        for path in ["/usr/bin", "\\Windows", "C:\\Games", "c:/games"] {
+            assert_eq!(check_path(path).as_deref(), Some("path must be relative"), "{}", path);
+        }
+    }
+
+    #[test]
+    fn empty_fields_and_unsorted_entries() {
+        let mut no_executables = preset("Empty", "", "");
+        no_executables.executables.clear();
+        let presets = [
+            preset("Zeta", "zeta.exe", "Zeta"),
+            preset(" ", "", ""),
+            no_executables,
+        ];
+        let issues = issues(&presets);
+
+        for expected in [
+            (Severity::Error, 1, "empty name"),
+            (Severity::Error, 1, "executables[0]: empty executable"),
+            (Severity::Error, 1, "executables[0]: empty path"),
+            (Severity::Error, 1, "not sorted (should come before \"Zeta\")"),
+            (Severity::Error, 2, "no executables"),
+        ] {
+            let expected = (expected.0, expected.1, expected.2.to_string());
+            assert!(issues.contains(&expected), "missing {:?} in {:?}", expected, issues);
+        }
+    }
+
+    #[test]
+    fn canonical_form_is_stable() {
+        let mut messy = preset(" Zeta ", " zeta.exe ", "Games\\Zeta\\ ");
+        messy.tags = vec![" rpg".into(), "".into(), "RPG".into(), "co-op ".into()];
+        messy.category = Some("  ".into());
+        messy.cwd = Some("bin\\".into());
+        let presets = [messy, preset("alpha", "alpha.exe", "Alpha/")];
+
+        let canonical = canonicalize_presets(&presets);
+        assert_eq!(canonical[0].name, "alpha");
+        assert_eq!(canonical[0].executables[0].path, "Alpha");
+        assert_eq!(canonical[1].name, "Zeta");
+        assert_eq!(canonical[1].executables[0].executable, "zeta.exe");
+        assert_eq!(canonical[1].executables[0].path, "Games/Zeta");
+        assert_eq!(canonical[1].tags, vec!["rpg".to_string(), "co-op".to_string()]);
+        assert_eq!(canonical[1].category, None);
+        assert_eq!(canonical[1].cwd.as_deref(), Some("bin"));
+        assert!(lint_presets(&canonical).is_empty());
+
+        // Volver a formatear no cambia nada
+        let once = serialize_presets(&canonical).unwrap();
+        let twice = serialize_presets(&canonicalize_presets(&canonical)).unwrap();
+        assert_eq!(once, twice);
+    }
+}
@@ -1,2 +1,10 @@
+pub mod history;
+pub mod hooks;
+pub mod importer;
+pub mod lint;
 pub mod presets;
 pub mod process;
+pub mod queue;
+pub mod quests;
+pub mod scheduler;
+pub mod schema;
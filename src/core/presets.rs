@@ -1,3 +1,4 @@
+use crate::core::schema::{parse_presets, serialize_presets, PresetParseError};
 use crate::shared::types::Preset;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -6,6 +7,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 const GITHUB_API_URL: &str =
     "https://api.github.com/repos/Nicolhetti/DSQProcess/releases/tags/presets";
 const PRESETS_FILE: &str = "presets.json";
+/// Assets del release, del preferido al antiguo. `presets.json` sigue en
+/// formato v0 para las versiones que no leen documentos.
+const PRESETS_ASSETS: [&str; 2] = ["presets.v2.json", "presets.json"];
 const CUSTOM_PRESETS_FILE: &str = "presets_custom.json";
 const PRESETS_METADATA_FILE: &str = "presets_metadata.json";
 const APP_UA: &str = concat!("DSQProcess/", env!("CARGO_PKG_VERSION"));
@@ -18,21 +22,31 @@ struct PresetsMetadata {
     hash: String,
 }
 
-/// Carga todos los presets (oficiales + personalizados)
-pub fn load_presets() -> Vec<Preset> {
-    let mut all_presets = Vec::new();
+/// Presets cargados junto con los errores encontrados al interpretarlos
+#[derive(Default)]
+pub struct LoadedPresets {
+    pub presets: Vec<Preset>,
+    pub errors: Vec<PresetParseError>,
+}
+
+/// Carga todos los presets (oficiales + personalizados).
+/// Las entradas inválidas se omiten y se devuelven como errores.
+pub fn load_presets() -> LoadedPresets {
+    let mut loaded = LoadedPresets::default();
 
     // Cargar presets oficiales
     match fs::read_to_string(PRESETS_FILE) {
-        Ok(data) => match serde_json::from_str::<Vec<Preset>>(&data) {
-            Ok(mut presets) => {
-                for preset in &mut presets {
+        Ok(data) => match parse_presets(PRESETS_FILE, &data) {
+            Ok(mut parsed) => {
+                for preset in &mut parsed.presets {
                     preset.is_custom = false;
                 }
-                all_presets.extend(presets);
+                loaded.presets.extend(parsed.presets);
+                loaded.errors.extend(parsed.errors);
             }
             Err(e) => {
-                log::error!("Failed to parse {}: {}", PRESETS_FILE, e);
+                log::error!("Failed to parse {}", e);
+                loaded.errors.push(e);
             }
         },
         Err(e) => {
@@ -42,15 +56,17 @@ pub fn load_presets() -> Vec<Preset> {
 
     // Cargar presets personalizados
     match fs::read_to_string(CUSTOM_PRESETS_FILE) {
-        Ok(data) => match serde_json::from_str::<Vec<Preset>>(&data) {
-            Ok(mut presets) => {
-                for preset in &mut presets {
+        Ok(data) => match parse_presets(CUSTOM_PRESETS_FILE, &data) {
+            Ok(mut parsed) => {
+                for preset in &mut parsed.presets {
                     preset.is_custom = true;
                 }
-                all_presets.extend(presets);
+                loaded.presets.extend(parsed.presets);
+                loaded.errors.extend(parsed.errors);
             }
             Err(e) => {
-                log::error!("Failed to parse {}: {}", CUSTOM_PRESETS_FILE, e);
+                log::error!("Failed to parse {}", e);
+                loaded.errors.push(e);
             }
         },
         Err(_) => {
@@ -59,11 +75,22 @@ pub fn load_presets() -> Vec<Preset> {
         }
     }
 
-    all_presets
+    loaded
+}
+
+/// Lee un archivo de presets sin omitir entradas: cualquier error lo invalida.
+/// Se usa antes de reescribir un archivo para no perder entradas inválidas.
+fn read_presets_strict(path: &str) -> Result<Vec<Preset>, Box<dyn std::error::Error>> {
+    let data = fs::read_to_string(path)?;
+    let mut parsed = parse_presets(path, &data)?;
+    if !parsed.errors.is_empty() {
+        return Err(Box::new(parsed.errors.swap_remove(0)));
+    }
+    Ok(parsed.presets)
 }
 
 /// Escritura atómica de archivos para evitar corrupción
-fn write_atomic(path: &str, contents: &[u8]) -> std::io::Result<()> {
+pub fn write_atomic(path: &str, contents: &[u8]) -> std::io::Result<()> {
     use std::{fs, io::Write, path::Path};
 
     let tmp = format!("{}.tmp", path);
@@ -87,16 +114,30 @@ fn write_atomic(path: &str, contents: &[u8]) -> std::io::Result<()> {
     Ok(())
 }
 
-/// Carga solo los presets personalizados
-fn load_custom_presets() -> Result<Vec<Preset>, Box<dyn std::error::Error>> {
-    let data = fs::read_to_string(CUSTOM_PRESETS_FILE)?;
-    let presets = serde_json::from_str(&data)?;
-    Ok(presets)
+/// Carga solo los presets oficiales
+pub fn load_official_presets() -> Result<Vec<Preset>, Box<dyn std::error::Error>> {
+    read_presets_strict(PRESETS_FILE)
+}
+
+/// Guarda los presets oficiales
+pub fn save_official_presets(presets: &[Preset]) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serialize_presets(presets)?;
+    write_atomic(PRESETS_FILE, json.as_bytes())?;
+    log::info!("Saved {} official presets", presets.len());
+    Ok(())
+}
+
+/// Carga solo los presets personalizados (vacío si el archivo no existe)
+pub fn load_custom_presets() -> Result<Vec<Preset>, Box<dyn std::error::Error>> {
+    if !std::path::Path::new(CUSTOM_PRESETS_FILE).exists() {
+        return Ok(Vec::new());
+    }
+    read_presets_strict(CUSTOM_PRESETS_FILE)
 }
 
 /// Guarda los presets personalizados
-fn save_custom_presets(presets: &[Preset]) -> Result<(), Box<dyn std::error::Error>> {
-    let json = serde_json::to_string_pretty(presets)?;
+pub fn save_custom_presets(presets: &[Preset]) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serialize_presets(presets)?;
     write_atomic(CUSTOM_PRESETS_FILE, json.as_bytes())?;
     log::info!("Saved {} custom presets", presets.len());
     Ok(())
@@ -104,7 +145,7 @@ fn save_custom_presets(presets: &[Preset]) -> Result<(), Box<dyn std::error::Err
 
 /// Agrega un nuevo preset personalizado
 pub fn add_preset(preset: Preset) -> Result<(), Box<dyn std::error::Error>> {
-    let mut custom_presets = load_custom_presets().unwrap_or_default();
+    let mut custom_presets = load_custom_presets()?;
 
     // Verificar duplicados (case-insensitive)
     if custom_presets
@@ -121,7 +162,7 @@ pub fn add_preset(preset: Preset) -> Result<(), Box<dyn std::error::Error>> {
 
 /// Elimina un preset personalizado por nombre
 pub fn delete_custom_preset(preset_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let mut custom_presets = load_custom_presets().unwrap_or_default();
+    let mut custom_presets = load_custom_presets()?;
     let original_len = custom_presets.len();
 
     custom_presets.retain(|p| !p.name.eq_ignore_ascii_case(preset_name));
@@ -139,7 +180,7 @@ pub fn edit_custom_preset(
     old_name: &str,
     new_preset: Preset,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut custom_presets = load_custom_presets().unwrap_or_default();
+    let mut custom_presets = load_custom_presets()?;
 
     // Verificar si el nuevo nombre ya existe (excluyendo el preset actual)
     if custom_presets.iter().any(|p| {
@@ -232,7 +273,7 @@ fn extract_remote_version(rel: &GitHubRelease) -> String {
     }
 
     // Último fallback: timestamp del asset
-    if let Some(asset) = rel.assets.iter().find(|a| a.name == "presets.json") {
+    if let Some(asset) = find_presets_asset(rel) {
         if let Some(ts) = &asset.updated_at {
             return ts.clone();
         }
@@ -242,6 +283,13 @@ fn extract_remote_version(rel: &GitHubRelease) -> String {
     rel.tag_name.clone()
 }
 
+/// Asset de presets del release, prefiriendo el formato actual
+fn find_presets_asset(rel: &GitHubRelease) -> Option<&GitHubAsset> {
+    PRESETS_ASSETS
+        .iter()
+        .find_map(|name| rel.assets.iter().find(|asset| asset.name == *name))
+}
+
 /// Extrae versión semántica de un string
 fn extract_version_from_string(s: &str) -> Option<String> {
     // Buscar patrón X.Y.Z (con o sin 'v' adelante)
@@ -348,12 +396,9 @@ pub fn update_presets_file() -> Result<(), Box<dyn std::error::Error>> {
 
     let release: GitHubRelease = response.json()?;
 
-    // Buscar el asset presets.json
-    let preset_asset = release
-        .assets
-        .iter()
-        .find(|asset| asset.name == "presets.json")
-        .ok_or("presets.json not found in release assets")?;
+    // Buscar el asset de presets (presets.v2.json o, en releases viejos, presets.json)
+    let preset_asset =
+        find_presets_asset(&release).ok_or("presets file not found in release assets")?;
 
     log::info!(
         "Downloading presets from: {}",
@@ -367,11 +412,17 @@ pub fn update_presets_file() -> Result<(), Box<dyn std::error::Error>> {
         .send()?
         .text()?;
 
-    // Validar que sea JSON válido antes de guardar
-    let validated_presets: Vec<Preset> = serde_json::from_str(&presets_content)
-        .map_err(|e| format!("Downloaded presets are not valid JSON: {}", e))?;
+    // Validar que sea un documento de presets válido antes de guardar
+    let validated = parse_presets(PRESETS_FILE, &presets_content)
+        .map_err(|e| format!("Downloaded presets are not valid: {}", e))?;
 
-    log::info!("Downloaded {} presets", validated_presets.len());
+    if !validated.errors.is_empty() {
+        log::warn!(
+            "Downloaded presets contain {} invalid entries",
+            validated.errors.len()
+        );
+    }
+    log::info!("Downloaded {} presets", validated.presets.len());
 
     // Guardar el archivo
     write_atomic(PRESETS_FILE, presets_content.as_bytes())?;
@@ -1,10 +1,27 @@
+use crate::core::history::ExitReason;
+use crate::core::hooks::{fire_hooks, HookPayload, SessionHook};
+use crate::core::lint::{check_executable, check_path};
+use crate::shared::process_table::ProcessTable;
+use crate::shared::types::{Preset, PresetExecutable};
+use chrono::{DateTime, Local};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use sysinfo::{Pid, ProcessesToUpdate, System};
+use sysinfo::Pid;
 
 pub struct ProcessMonitor {
     processes: Arc<Mutex<Vec<ProcessInfo>>>,
-    system: Arc<Mutex<System>>, // Reutilizar System para mejor performance
+    /// Handles de los procesos hijos, para leer su código de salida
+    children: Arc<Mutex<HashMap<u32, Child>>>,
+    /// Motivo de cierre de los grupos detenidos desde la app
+    stop_reasons: Arc<Mutex<HashMap<u64, ExitReason>>>,
+    /// Compartida con la detección de Discord
+    table: ProcessTable,
+    next_group_id: AtomicU64,
+    /// Hooks que se ejecutan al iniciar y terminar cada sesión
+    hooks: Arc<Mutex<Vec<SessionHook>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -12,30 +29,162 @@ pub struct ProcessInfo {
     pub pid: u32,
     pub exe_name: String,
     pub exe_path: PathBuf,
+    /// Los procesos de un mismo preset comparten grupo y terminan juntos
+    pub group_id: u64,
+    pub preset_name: String,
+    pub started_at: DateTime<Local>,
+    /// Duración con la que se inició la simulación
+    pub duration_minutes: u64,
+}
+
+/// Sesión simulada en curso, con todos los procesos de su grupo
+#[derive(Clone, Debug)]
+pub struct ActiveSession {
+    pub group_id: u64,
+    pub preset_name: String,
+    pub executables: Vec<String>,
+    pub started_at: DateTime<Local>,
+    pub duration_minutes: u64,
+}
+
+impl ActiveSession {
+    pub fn elapsed(&self) -> std::time::Duration {
+        (Local::now() - self.started_at).to_std().unwrap_or_default()
+    }
+
+    pub fn remaining(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.duration_minutes * 60).saturating_sub(self.elapsed())
+    }
+}
+
+/// Sesión simulada cuyo grupo de procesos terminó
+#[derive(Clone, Debug)]
+pub struct FinishedSession {
+    pub group_id: u64,
+    pub preset_name: String,
+    pub executables: Vec<String>,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    pub exit_reason: ExitReason,
+}
+
+impl FinishedSession {
+    pub fn duration(&self) -> std::time::Duration {
+        (self.ended_at - self.started_at)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+/// Variables que se pueden usar en argumentos, directorio y entorno
+pub const TEMPLATE_PLACEHOLDERS: &[&str] =
+    &["{preset_name}", "{duration}", "{executable}", "{path}"];
+
+/// Argumentos, directorio de trabajo y entorno con los que se inician
+/// los procesos falsos
+#[derive(Clone, Debug, Default)]
+pub struct LaunchOptions {
+    pub preset_name: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+impl LaunchOptions {
+    pub fn from_preset(preset: &Preset) -> Self {
+        Self {
+            preset_name: preset.name.clone(),
+            args: preset.args.clone(),
+            cwd: preset.cwd.clone(),
+            env: preset.env.clone(),
+        }
+    }
+
+    /// Reemplaza las variables de plantilla para un ejecutable concreto
+    fn render(&self, template: &str, exe: &PresetExecutable, duration_min: u64) -> String {
+        template
+            .replace("{preset_name}", &self.preset_name)
+            .replace("{duration}", &duration_min.to_string())
+            .replace("{executable}", &exe.executable)
+            .replace("{path}", &exe.path)
+    }
+}
+
+/// Proceso falso recién creado, antes de registrarlo en el monitor
+#[derive(Debug)]
+pub struct SpawnedProcess {
+    pub pid: u32,
+    pub exe_name: String,
+    pub exe_path: PathBuf,
+    pub child: Child,
 }
 
 impl ProcessMonitor {
     pub fn new() -> Self {
         Self {
             processes: Arc::new(Mutex::new(Vec::new())),
-            system: Arc::new(Mutex::new(System::new_all())),
+            children: Arc::new(Mutex::new(HashMap::new())),
+            stop_reasons: Arc::new(Mutex::new(HashMap::new())),
+            table: ProcessTable::new(),
+            next_group_id: AtomicU64::new(1),
+            hooks: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    pub fn add_process(&self, pid: u32, exe_name: String, exe_path: PathBuf) {
-        if let Ok(mut procs) = self.processes.lock() {
-            procs.push(ProcessInfo {
-                pid,
-                exe_name: exe_name.clone(),
-                exe_path,
-            });
-            log::info!("Added process to monitor: {} (PID: {})", exe_name, pid);
+    pub fn process_table(&self) -> &ProcessTable {
+        &self.table
+    }
+
+    pub fn set_hooks(&self, hooks: Vec<SessionHook>) {
+        if let Ok(mut current) = self.hooks.lock() {
+            *current = hooks;
+        }
+    }
+
+    /// Ejecuta los hooks del evento en segundo plano
+    fn fire_hooks(&self, payload: HookPayload) -> Option<std::thread::JoinHandle<()>> {
+        let hooks = self.hooks.lock().ok()?;
+        fire_hooks(&hooks, payload)
+    }
+
+    /// Registra un grupo de procesos que se monitorean juntos
+    pub fn add_process_group(
+        &self,
+        preset_name: &str,
+        duration_minutes: u64,
+        spawned: Vec<SpawnedProcess>,
+    ) -> u64 {
+        let group_id = self.next_group_id.fetch_add(1, Ordering::Relaxed);
+        let started_at = Local::now();
+        let executables: Vec<String> = spawned.iter().map(|p| p.exe_name.clone()).collect();
+
+        if let (Ok(mut procs), Ok(mut children)) = (self.processes.lock(), self.children.lock()) {
+            for process in spawned {
+                log::info!(
+                    "Added process to monitor: {} (PID: {}, group {})",
+                    process.exe_name,
+                    process.pid,
+                    group_id
+                );
+                children.insert(process.pid, process.child);
+                procs.push(ProcessInfo {
+                    pid: process.pid,
+                    exe_name: process.exe_name,
+                    exe_path: process.exe_path,
+                    group_id,
+                    preset_name: preset_name.to_string(),
+                    started_at,
+                    duration_minutes,
+                });
+            }
         } else {
             log::error!("Failed to acquire lock for adding process");
         }
+
+        self.fire_hooks(HookPayload::started(group_id, preset_name, executables, started_at));
+        group_id
     }
 
-    #[allow(dead_code)]
     pub fn get_active_processes(&self) -> Vec<ProcessInfo> {
         self.processes
             .lock()
@@ -44,75 +193,212 @@ impl ProcessMonitor {
             .unwrap_or_default()
     }
 
-    pub fn check_and_remove_dead_processes(&self) -> Vec<String> {
-        // Refresh system info primero
-        if let Ok(mut sys) = self.system.lock() {
-            sys.refresh_processes(ProcessesToUpdate::All, true);
-        } else {
-            log::error!("Failed to acquire system lock for refresh");
-            return Vec::new();
+    /// Sesiones activas agrupadas por grupo, de la más antigua a la más reciente
+    pub fn active_sessions(&self) -> Vec<ActiveSession> {
+        let mut sessions: Vec<ActiveSession> = Vec::new();
+        for process in self.get_active_processes() {
+            match sessions
+                .iter_mut()
+                .find(|session| session.group_id == process.group_id)
+            {
+                Some(session) => session.executables.push(process.exe_name),
+                None => sessions.push(ActiveSession {
+                    group_id: process.group_id,
+                    preset_name: process.preset_name,
+                    executables: vec![process.exe_name],
+                    started_at: process.started_at,
+                    duration_minutes: process.duration_minutes,
+                }),
+            }
         }
+        sessions.sort_by_key(|session| session.group_id);
+        sessions
+    }
 
-        let mut removed = Vec::new();
+    /// Detiene los procesos de un grupo. La sesión se informa con el motivo
+    /// indicado en la próxima revisión de procesos terminados.
+    pub fn stop_group(&self, group_id: u64, reason: ExitReason) {
+        let (Ok(procs), Ok(mut children)) = (self.processes.lock(), self.children.lock()) else {
+            log::error!("Failed to acquire process monitor locks");
+            return;
+        };
 
-        if let Ok(mut procs) = self.processes.lock() {
-            let sys = match self.system.lock() {
-                Ok(s) => s,
-                Err(_) => {
-                    log::error!("Failed to acquire system lock");
-                    return removed;
+        if let Ok(mut reasons) = self.stop_reasons.lock() {
+            reasons.insert(group_id, reason);
+        }
+
+        for proc_info in procs.iter().filter(|p| p.group_id == group_id) {
+            if let Some(child) = children.get_mut(&proc_info.pid) {
+                log::info!(
+                    "Stopping {} (PID: {}) in group {}",
+                    proc_info.exe_name,
+                    proc_info.pid,
+                    group_id
+                );
+                if let Err(e) = child.kill() {
+                    log::warn!("Failed to stop process {}: {}", proc_info.pid, e);
                 }
+            }
+        }
+    }
+
+    /// Detiene todos los grupos monitoreados
+    pub fn stop_all(&self, reason: ExitReason) {
+        let mut groups: Vec<u64> = match self.processes.lock() {
+            Ok(procs) => procs.iter().map(|p| p.group_id).collect(),
+            Err(_) => return,
+        };
+        groups.sort_unstable();
+        groups.dedup();
+
+        for group_id in groups {
+            self.stop_group(group_id, reason);
+        }
+    }
+
+    /// Elimina los procesos terminados. Si un proceso de un grupo termina,
+    /// se cierran también los demás procesos de ese grupo.
+    /// Devuelve una sesión por cada grupo terminado.
+    pub fn check_and_remove_dead_processes(&self) -> Vec<FinishedSession> {
+        // Los hijos propios se revisan con `try_wait`; solo se refrescan
+        // los procesos de los que no tenemos handle
+        let untracked: Vec<u32> = match (self.processes.lock(), self.children.lock()) {
+            (Ok(procs), Ok(children)) => procs
+                .iter()
+                .map(|proc_info| proc_info.pid)
+                .filter(|pid| !children.contains_key(pid))
+                .collect(),
+            _ => {
+                log::error!("Failed to acquire process monitor locks");
+                return Vec::new();
+            }
+        };
+        self.table.refresh_pids(&untracked);
+
+        let mut removed: Vec<FinishedSession> = Vec::new();
+        let ended_at = Local::now();
+
+        // Se sacan los procesos de los grupos terminados con las listas
+        // bloqueadas; detenerlos y borrar los ejecutables se hace después
+        let dead = {
+            let (Ok(mut procs), Ok(mut children), Some(sys)) =
+                (self.processes.lock(), self.children.lock(), self.table.lock())
+            else {
+                log::error!("Failed to acquire process monitor locks");
+                return removed;
             };
 
-            procs.retain(|proc_info| {
-                let pid = Pid::from_u32(proc_info.pid);
-                let is_alive = sys.process(pid).is_some();
-
-                if !is_alive {
-                    log::info!(
-                        "Process {} (PID: {}) has terminated",
-                        proc_info.exe_name,
-                        proc_info.pid
-                    );
-                    removed.push(proc_info.exe_name.clone());
-
-                    // Intentar eliminar el ejecutable de forma segura
-                    if proc_info.exe_path.exists() {
-                        // Esperar un poco para asegurar que el proceso se liberó
-                        std::thread::sleep(std::time::Duration::from_millis(100));
-
-                        match std::fs::remove_file(&proc_info.exe_path) {
-                            Ok(_) => {
-                                log::info!(
-                                    "Successfully deleted executable: {}",
-                                    proc_info.exe_path.display()
-                                );
-                            }
-                            Err(e) => {
-                                log::warn!(
-                                    "Failed to delete executable {} (will retry later): {}",
-                                    proc_info.exe_path.display(),
-                                    e
-                                );
-                                // No fallar silenciosamente, pero tampoco bloquear
-                            }
+            // El primer proceso terminado de cada grupo decide el motivo de cierre
+            let mut dead_groups: Vec<(u64, ExitReason)> = Vec::new();
+            for proc_info in procs.iter() {
+                if dead_groups.iter().any(|(group_id, _)| *group_id == proc_info.group_id) {
+                    continue;
+                }
+
+                let exit_reason = match children.get_mut(&proc_info.pid) {
+                    Some(child) => match child.try_wait() {
+                        Ok(Some(status)) => Some(ExitReason::from_exit_code(status.code())),
+                        Ok(None) => None,
+                        Err(e) => {
+                            log::warn!("Failed to query process {}: {}", proc_info.pid, e);
+                            Some(ExitReason::Crash)
                         }
-                    }
+                    },
+                    None => sys
+                        .process(Pid::from_u32(proc_info.pid))
+                        .is_none()
+                        .then_some(ExitReason::Crash),
+                };
+
+                if let Some(exit_reason) = exit_reason {
+                    let exit_reason = self
+                        .stop_reasons
+                        .lock()
+                        .ok()
+                        .and_then(|mut reasons| reasons.remove(&proc_info.group_id))
+                        .unwrap_or(exit_reason);
+                    dead_groups.push((proc_info.group_id, exit_reason));
                 }
+            }
 
-                is_alive
+            let mut dead: Vec<(ProcessInfo, Option<Child>, ExitReason)> = Vec::new();
+            procs.retain(|proc_info| {
+                match dead_groups
+                    .iter()
+                    .find(|(group_id, _)| *group_id == proc_info.group_id)
+                {
+                    Some(&(_, exit_reason)) => {
+                        let child = children.remove(&proc_info.pid);
+                        dead.push((proc_info.clone(), child, exit_reason));
+                        false
+                    }
+                    None => true,
+                }
             });
-        } else {
-            log::error!("Failed to acquire processes lock");
+            dead
+        };
+
+        let mut exe_paths: Vec<PathBuf> = Vec::new();
+        for (proc_info, child, exit_reason) in dead {
+            stop_process(&proc_info, child, &self.table);
+
+            match removed
+                .iter_mut()
+                .find(|session| session.group_id == proc_info.group_id)
+            {
+                Some(session) => session.executables.push(proc_info.exe_name.clone()),
+                None => {
+                    removed.push(FinishedSession {
+                        group_id: proc_info.group_id,
+                        preset_name: proc_info.preset_name.clone(),
+                        executables: vec![proc_info.exe_name.clone()],
+                        started_at: proc_info.started_at,
+                        ended_at,
+                        exit_reason,
+                    });
+                }
+            }
+            exe_paths.push(proc_info.exe_path);
         }
 
+        // Sin ningún lock tomado: cada borrado espera a que se libere el archivo
+        for exe_path in &exe_paths {
+            remove_executable(exe_path);
+        }
+
+        for session in &removed {
+            self.fire_hooks(HookPayload::from(session));
+        }
         removed
     }
 
-    /// Limpia todos los procesos y ejecutables pendientes (útil para shutdown)
-    pub fn cleanup_all(&self) {
+    /// Limpia todos los procesos y ejecutables pendientes (útil para shutdown).
+    /// Devuelve las sesiones que seguían activas.
+    pub fn cleanup_all(&self) -> Vec<FinishedSession> {
+        let mut sessions: Vec<FinishedSession> = Vec::new();
+        let ended_at = Local::now();
+
+        if let Ok(mut children) = self.children.lock() {
+            children.clear();
+        }
+
         if let Ok(mut procs) = self.processes.lock() {
             for proc_info in procs.drain(..) {
+                match sessions
+                    .iter_mut()
+                    .find(|session| session.group_id == proc_info.group_id)
+                {
+                    Some(session) => session.executables.push(proc_info.exe_name.clone()),
+                    None => sessions.push(FinishedSession {
+                        group_id: proc_info.group_id,
+                        preset_name: proc_info.preset_name.clone(),
+                        executables: vec![proc_info.exe_name.clone()],
+                        started_at: proc_info.started_at,
+                        ended_at,
+                        exit_reason: ExitReason::ParentShutdown,
+                    }),
+                }
+
                 if proc_info.exe_path.exists() {
                     if let Err(e) = std::fs::remove_file(&proc_info.exe_path) {
                         log::warn!(
@@ -124,6 +410,67 @@ impl ProcessMonitor {
                 }
             }
         }
+
+        // Al cerrar la app se espera a los hooks para que no se corten
+        // (cada uno tiene su propio tiempo máximo)
+        let pending: Vec<_> = sessions
+            .iter()
+            .filter_map(|session| self.fire_hooks(HookPayload::from(session)))
+            .collect();
+        for handle in pending {
+            let _ = handle.join();
+        }
+
+        sessions
+    }
+}
+
+/// Detiene un proceso de un grupo terminado, si sigue en ejecución.
+/// La tabla solo se bloquea para los procesos sin handle propio.
+fn stop_process(proc_info: &ProcessInfo, child: Option<Child>, table: &ProcessTable) {
+    let running = match child {
+        Some(mut child) => match child.try_wait() {
+            Ok(None) => {
+                log::info!(
+                    "Stopping {} (PID: {}) because its group {} ended",
+                    proc_info.exe_name,
+                    proc_info.pid,
+                    proc_info.group_id
+                );
+                if let Err(e) = child.kill() {
+                    log::warn!("Failed to stop process {}: {}", proc_info.pid, e);
+                }
+                let _ = child.wait();
+                true
+            }
+            _ => false,
+        },
+        None => match table.lock().as_deref().and_then(|sys| {
+            sys.process(Pid::from_u32(proc_info.pid))
+        }) {
+            Some(process) => {
+                log::info!(
+                    "Stopping {} (PID: {}) because its group {} ended",
+                    proc_info.exe_name,
+                    proc_info.pid,
+                    proc_info.group_id
+                );
+                if !process.kill() {
+                    log::warn!("Failed to stop process {}", proc_info.pid);
+                }
+                let _ = process.wait();
+                true
+            }
+            None => false,
+        },
+    };
+
+    if !running {
+        log::info!(
+            "Process {} (PID: {}) has terminated",
+            proc_info.exe_name,
+            proc_info.pid
+        );
     }
 }
 
@@ -135,16 +482,77 @@ impl Default for ProcessMonitor {
 
 impl Drop for ProcessMonitor {
     fn drop(&mut self) {
-        self.cleanup_all();
+        let _ = self.cleanup_all();
+    }
+}
+
+/// Elimina el ejecutable copiado de un proceso terminado
+fn remove_executable(exe_path: &Path) {
+    if !exe_path.exists() {
+        return;
+    }
+
+    // Esperar un poco para asegurar que el proceso se liberó
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    match std::fs::remove_file(exe_path) {
+        Ok(_) => {
+            log::info!("Successfully deleted executable: {}", exe_path.display());
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to delete executable {} (will retry later): {}",
+                exe_path.display(),
+                e
+            );
+            // No fallar silenciosamente, pero tampoco bloquear
+        }
+    }
+}
+
+/// Crea todos los procesos falsos de un preset. Si alguno falla,
+/// se detienen los que ya se habían iniciado.
+pub fn create_fake_process_group(
+    executables: &[PresetExecutable],
+    launch: &LaunchOptions,
+    duration_min: u64,
+) -> std::io::Result<Vec<SpawnedProcess>> {
+    let mut spawned: Vec<SpawnedProcess> = Vec::new();
+
+    for exe in executables {
+        match create_fake_process(exe, launch, duration_min) {
+            Ok((child, exe_path)) => {
+                spawned.push(SpawnedProcess {
+                    pid: child.id(),
+                    exe_name: exe.executable.clone(),
+                    exe_path,
+                    child,
+                });
+            }
+            Err(e) => {
+                for mut process in spawned {
+                    log::warn!("Rolling back process {} (PID: {})", process.exe_name, process.pid);
+                    let _ = process.child.kill();
+                    let _ = process.child.wait();
+                    remove_executable(&process.exe_path);
+                }
+                return Err(e);
+            }
+        }
     }
+
+    Ok(spawned)
 }
 
 /// Crea un proceso falso copiando DSQChild.exe
-pub fn create_fake_process(
-    folder: &str,
-    exe_name: &str,
+fn create_fake_process(
+    exe: &PresetExecutable,
+    launch: &LaunchOptions,
     duration_min: u64,
-) -> std::io::Result<(u32, PathBuf)> {
+) -> std::io::Result<(Child, PathBuf)> {
+    let folder = exe.path.as_str();
+    let exe_name = exe.executable.as_str();
+
     // Validación de entrada
     if exe_name.is_empty() {
         return Err(std::io::Error::new(
@@ -153,6 +561,15 @@ pub fn create_fake_process(
         ));
     }
 
+    // La ruta puede venir de la API o de un archivo ajeno: el ejecutable
+    // se borra y se copia ahí, así que no puede salir de Games
+    if let Some(problem) = check_executable(exe_name).or_else(|| check_path(folder)) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid executable {:?} in {:?}: {}", exe_name, folder, problem),
+        ));
+    }
+
     // Agregar automáticamente "Games/" si la ruta no empieza con ella
     let full_path = if folder.starts_with("Games/") || folder.starts_with("Games\\") {
         folder.to_string()
@@ -217,9 +634,47 @@ pub fn create_fake_process(
         return Err(e);
     }
 
+    let mut command = std::process::Command::new(&new_exe_path);
+    command
+        .args(
+            launch
+                .args
+                .iter()
+                .map(|arg| launch.render(arg, exe, duration_min)),
+        )
+        .envs(
+            launch
+                .env
+                .iter()
+                .map(|(key, value)| (key, launch.render(value, exe, duration_min))),
+        )
+        // DSQChild lee la duración y la carpeta de la app desde el entorno,
+        // así los argumentos quedan libres para el preset
+        .env("DSQ_DURATION_MINUTES", duration_min.to_string())
+        .env("DSQ_APP_DIR", std::env::current_dir()?);
+
+    if let Some(cwd) = &launch.cwd {
+        let cwd = launch.render(cwd, exe, duration_min);
+        let relative = Path::new(&cwd);
+        if relative.is_absolute()
+            || relative
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            let _ = std::fs::remove_file(&new_exe_path);
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Working directory must be relative to the preset path: {}", cwd),
+            ));
+        }
+
+        let working_dir = target_folder.join(relative);
+        std::fs::create_dir_all(&working_dir)?;
+        command.current_dir(working_dir);
+    }
+
     // Spawn proceso con manejo de errores
-    let child = std::process::Command::new(&new_exe_path)
-        .arg(duration_min.to_string())
+    let child = command
         .spawn()
         .map_err(|e| {
             log::error!("Failed to spawn process {}: {}", new_exe_path.display(), e);
@@ -228,12 +683,89 @@ pub fn create_fake_process(
             e
         })?;
 
-    let pid = child.id();
     log::info!(
         "Successfully created fake process {} (PID: {})",
         exe_name,
-        pid
+        child.id()
     );
 
-    Ok((pid, new_exe_path))
+    Ok((child, new_exe_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::in_temp_dir;
+
+    fn executable(executable: &str, path: &str) -> PresetExecutable {
+        PresetExecutable {
+            executable: executable.to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fake_process_rejects_paths_outside_games() {
+        in_temp_dir(|dir| {
+            std::fs::write("victim.txt", "keep").unwrap();
+            let launch = LaunchOptions::default();
+
+            for exe in [
+                executable("victim.txt", ".."),
+                executable("victim.txt", "../.."),
+                executable("victim.txt", "Games/../.."),
+                executable("../victim.txt", "Game"),
+                executable("..\\victim.txt", "Game"),
+                executable("victim.txt", &dir.display().to_string()),
+                executable("victim.txt", "C:\\Windows"),
+            ] {
+                let error =
+                    create_fake_process_group(std::slice::from_ref(&exe), &launch, 1).unwrap_err();
+                assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput, "{:?}", exe);
+            }
+
+            assert_eq!(std::fs::read_to_string("victim.txt").unwrap(), "keep");
+            assert!(!dir.join("Games").exists());
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ended_group_stops_the_rest_and_removes_executables() {
+        in_temp_dir(|dir| {
+            let spawn = |name: &str, program: &str, args: &[&str]| {
+                let exe_path = dir.join(name);
+                std::fs::write(&exe_path, "").unwrap();
+                let child = std::process::Command::new(program).args(args).spawn().unwrap();
+                SpawnedProcess {
+                    pid: child.id(),
+                    exe_name: name.to_string(),
+                    exe_path,
+                    child,
+                }
+            };
+
+            let monitor = ProcessMonitor::new();
+            let game = spawn("game.exe", "true", &[]);
+            let launcher = spawn("launcher.exe", "sleep", &["30"]);
+            let group_id = monitor.add_process_group("Game", 1, vec![game, launcher]);
+
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+            let finished = loop {
+                let finished = monitor.check_and_remove_dead_processes();
+                if !finished.is_empty() || std::time::Instant::now() > deadline {
+                    break finished;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            };
+
+            assert_eq!(finished.len(), 1);
+            assert_eq!(finished[0].group_id, group_id);
+            assert_eq!(finished[0].executables, vec!["game.exe", "launcher.exe"]);
+            assert!(monitor.get_active_processes().is_empty());
+            assert!(!dir.join("game.exe").exists());
+            assert!(!dir.join("launcher.exe").exists());
+        });
+    }
 }
@@ -0,0 +1,161 @@
+use crate::core::presets::write_atomic;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const QUESTS_FILE: &str = "quests.json";
+
+/// Misión de Discord vinculada a un preset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quest {
+    pub id: u64,
+    /// Nombre del preset que cumple la misión
+    pub preset: String,
+    pub required_minutes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    /// Tiempo simulado acumulado de las sesiones terminadas
+    #[serde(default)]
+    pub progress_seconds: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Local>>,
+}
+
+impl Quest {
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some()
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires < today)
+    }
+
+    /// Progreso entre 0 y 1
+    pub fn progress(&self) -> f32 {
+        let required = self.required_minutes.max(1) * 60;
+        (self.progress_seconds as f32 / required as f32).min(1.0)
+    }
+
+    /// Minutos que faltan, redondeando hacia arriba
+    pub fn remaining_minutes(&self) -> u64 {
+        let remaining = (self.required_minutes * 60).saturating_sub(self.progress_seconds);
+        remaining.div_ceil(60)
+    }
+}
+
+/// Carga las misiones guardadas (vacío si el archivo no existe)
+pub fn load_quests() -> Result<Vec<Quest>, Box<dyn std::error::Error>> {
+    if !std::path::Path::new(QUESTS_FILE).exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(QUESTS_FILE)?;
+    let mut quests: Vec<Quest> = serde_json::from_str(&data)
+        .map_err(|e| format!("{}: {}", QUESTS_FILE, e))?;
+    sort_quests(&mut quests);
+    Ok(quests)
+}
+
+pub fn save_quests(quests: &[Quest]) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(quests)?;
+    write_atomic(QUESTS_FILE, json.as_bytes())?;
+    log::info!("Saved {} quests", quests.len());
+    Ok(())
+}
+
+pub fn next_quest_id(quests: &[Quest]) -> u64 {
+    quests.iter().map(|quest| quest.id).max().unwrap_or(0) + 1
+}
+
+/// Ordena las misiones pendientes por fecha límite más cercana;
+/// las que no vencen y las completadas van al final
+pub fn sort_quests(quests: &mut [Quest]) {
+    quests.sort_by_key(|quest| {
+        (
+            quest.is_complete(),
+            quest.expires.is_none(),
+            quest.expires,
+            quest.id,
+        )
+    });
+}
+
+/// Cambios que dejó una sesión en las misiones
+#[derive(Debug, Default)]
+pub struct QuestProgress {
+    /// Alguna misión sumó tiempo y hay que guardarlas
+    pub changed: bool,
+    /// Nombres de las misiones que se completaron
+    pub completed: Vec<String>,
+}
+
+/// Suma una sesión terminada a las misiones pendientes de ese preset
+pub fn record_session(quests: &mut [Quest], preset_name: &str, duration: Duration) -> QuestProgress {
+    let now = Local::now();
+    let mut progress = QuestProgress::default();
+
+    for quest in quests.iter_mut() {
+        if quest.is_complete()
+            || quest.is_expired(now.date_naive())
+            || !quest.preset.eq_ignore_ascii_case(preset_name)
+        {
+            continue;
+        }
+
+        quest.progress_seconds += duration.as_secs();
+        progress.changed = true;
+        log::info!(
+            "Quest {} ({}) progress: {}/{} min",
+            quest.id,
+            quest.preset,
+            quest.progress_seconds / 60,
+            quest.required_minutes
+        );
+
+        if quest.progress_seconds >= quest.required_minutes * 60 {
+            quest.completed_at = Some(now);
+            progress.completed.push(quest.preset.clone());
+        }
+    }
+
+    if !progress.completed.is_empty() {
+        sort_quests(quests);
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest(preset: &str, required_minutes: u64) -> Quest {
+        Quest {
+            id: 1,
+            preset: preset.to_string(),
+            required_minutes,
+            expires: None,
+            notes: String::new(),
+            progress_seconds: 0,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn session_of_other_preset_changes_nothing() {
+        let mut quests = vec![quest("Valorant", 15)];
+        let progress = record_session(&mut quests, "Apex Legends", Duration::from_secs(600));
+        assert!(!progress.changed);
+        assert!(progress.completed.is_empty());
+        assert_eq!(quests[0].progress_seconds, 0);
+    }
+
+    #[test]
+    fn session_completes_quest() {
+        let mut quests = vec![quest("Valorant", 15)];
+        let progress = record_session(&mut quests, "valorant", Duration::from_secs(900));
+        assert!(progress.changed);
+        assert_eq!(progress.completed, vec!["Valorant".to_string()]);
+        assert!(quests[0].is_complete());
+    }
+}
@@ -0,0 +1,251 @@
+use std::time::{Duration, Instant};
+
+/// Margen antes de detener un elemento que no se cerró solo a tiempo
+const OVERDUE_GRACE: Duration = Duration::from_secs(10);
+
+/// Preset y duración pendientes en la cola
+#[derive(Debug, Clone)]
+pub struct QueueItem {
+    pub preset: String,
+    pub duration_minutes: u64,
+}
+
+/// Elemento de la cola que se está simulando
+#[derive(Debug, Clone)]
+pub struct ActiveQueueItem {
+    pub item: QueueItem,
+    pub group_id: u64,
+    pub started_at: Instant,
+    /// Ya se pidió detener el grupo (por salto o por tiempo cumplido)
+    pub stop_requested: bool,
+}
+
+impl ActiveQueueItem {
+    fn duration(&self) -> Duration {
+        Duration::from_secs(self.item.duration_minutes * 60)
+    }
+
+    /// Progreso entre 0 y 1
+    pub fn progress(&self) -> f32 {
+        let total = self.duration().as_secs_f32().max(1.0);
+        (self.started_at.elapsed().as_secs_f32() / total).min(1.0)
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.duration().saturating_sub(self.started_at.elapsed())
+    }
+}
+
+/// Cola de simulaciones que se ejecutan una después de otra
+#[derive(Debug, Default)]
+pub struct SimulationQueue {
+    pub pending: Vec<QueueItem>,
+    pub current: Option<ActiveQueueItem>,
+    /// La cola avanza sola mientras está activa; en pausa el elemento
+    /// actual termina pero no se inicia el siguiente
+    pub running: bool,
+}
+
+impl SimulationQueue {
+    pub fn push(&mut self, preset: &str, duration_minutes: u64) {
+        self.pending.push(QueueItem {
+            preset: preset.to_string(),
+            duration_minutes,
+        });
+    }
+
+    pub fn move_up(&mut self, index: usize) {
+        if index > 0 && index < self.pending.len() {
+            self.pending.swap(index - 1, index);
+        }
+    }
+
+    pub fn move_down(&mut self, index: usize) {
+        if index + 1 < self.pending.len() {
+            self.pending.swap(index, index + 1);
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.pending.len() {
+            self.pending.remove(index);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.current.is_none()
+    }
+
+    /// Siguiente elemento a iniciar, si la cola está activa y libre
+    pub fn next_ready(&mut self) -> Option<QueueItem> {
+        if !self.running || self.current.is_some() {
+            return None;
+        }
+        if self.pending.is_empty() {
+            log::info!("Simulation queue finished");
+            self.running = false;
+            return None;
+        }
+        Some(self.pending.remove(0))
+    }
+
+    pub fn start(&mut self, item: QueueItem, group_id: u64) {
+        log::info!(
+            "Queue started {} for {} min (group {})",
+            item.preset,
+            item.duration_minutes,
+            group_id
+        );
+        self.current = Some(ActiveQueueItem {
+            item,
+            group_id,
+            started_at: Instant::now(),
+            stop_requested: false,
+        });
+    }
+
+    /// Marca como terminado el elemento actual si pertenece al grupo
+    pub fn finish_group(&mut self, group_id: u64) -> bool {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| current.group_id == group_id)
+        {
+            self.current = None;
+            return true;
+        }
+        false
+    }
+
+    /// Grupo del elemento actual que superó su tiempo y sigue abierto
+    pub fn overdue_group(&mut self) -> Option<u64> {
+        let current = self.current.as_mut()?;
+        if current.stop_requested || current.started_at.elapsed() < current.duration() + OVERDUE_GRACE {
+            return None;
+        }
+        current.stop_requested = true;
+        Some(current.group_id)
+    }
+
+    /// Grupo a detener para saltar al siguiente elemento
+    pub fn skip(&mut self) -> Option<u64> {
+        let current = self.current.as_mut()?;
+        if current.stop_requested {
+            return None;
+        }
+        current.stop_requested = true;
+        Some(current.group_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(presets: &[&str]) -> SimulationQueue {
+        let mut queue = SimulationQueue::default();
+        for preset in presets {
+            queue.push(preset, 1);
+        }
+        queue
+    }
+
+    fn pending(queue: &SimulationQueue) -> Vec<&str> {
+        queue.pending.iter().map(|item| item.preset.as_str()).collect()
+    }
+
+    /// Mueve el inicio del elemento actual hacia atrás
+    fn started_ago(queue: &mut SimulationQueue, elapsed: Duration) {
+        let current = queue.current.as_mut().unwrap();
+        current.started_at = Instant::now().checked_sub(elapsed).unwrap();
+    }
+
+    #[test]
+    fn items_run_back_to_back() {
+        let mut queue = queue(&["A", "B"]);
+        assert!(queue.next_ready().is_none());
+
+        queue.running = true;
+        let first = queue.next_ready().unwrap();
+        assert_eq!(first.preset, "A");
+        queue.start(first, 1);
+        // No se inicia otro mientras el actual sigue abierto
+        assert!(queue.next_ready().is_none());
+
+        assert!(!queue.finish_group(2));
+        assert!(queue.finish_group(1));
+        let second = queue.next_ready().unwrap();
+        assert_eq!(second.preset, "B");
+        queue.start(second, 2);
+        assert!(queue.finish_group(2));
+
+        assert!(queue.next_ready().is_none());
+        assert!(!queue.running);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn paused_queue_keeps_pending_items() {
+        let mut queue = queue(&["A"]);
+        queue.running = true;
+        let item = queue.next_ready().unwrap();
+        queue.start(item, 1);
+        queue.push("B", 1);
+
+        queue.running = false;
+        assert!(queue.finish_group(1));
+        assert!(queue.next_ready().is_none());
+        assert_eq!(pending(&queue), ["B"]);
+    }
+
+    #[test]
+    fn skip_stops_the_current_group_once() {
+        let mut queue = queue(&["A"]);
+        assert!(queue.skip().is_none());
+
+        queue.running = true;
+        let item = queue.next_ready().unwrap();
+        queue.start(item, 7);
+
+        assert_eq!(queue.skip(), Some(7));
+        assert!(queue.skip().is_none());
+        assert!(queue.current.as_ref().unwrap().stop_requested);
+    }
+
+    #[test]
+    fn overdue_after_the_grace_period() {
+        let mut queue = queue(&["A"]);
+        queue.running = true;
+        let item = queue.next_ready().unwrap();
+        queue.start(item, 3);
+
+        started_ago(&mut queue, Duration::from_secs(60));
+        assert!(queue.overdue_group().is_none());
+
+        started_ago(&mut queue, Duration::from_secs(60) + OVERDUE_GRACE);
+        assert_eq!(queue.overdue_group(), Some(3));
+        // Ya se pidió detenerlo; ni se repite ni se puede saltar
+        assert!(queue.overdue_group().is_none());
+        assert!(queue.skip().is_none());
+    }
+
+    #[test]
+    fn reordering_stops_at_the_ends() {
+        let mut queue = queue(&["A", "B", "C"]);
+
+        queue.move_up(0);
+        queue.move_down(2);
+        queue.move_up(3);
+        queue.move_down(3);
+        assert_eq!(pending(&queue), ["A", "B", "C"]);
+
+        queue.move_up(2);
+        assert_eq!(pending(&queue), ["A", "C", "B"]);
+        queue.move_down(0);
+        assert_eq!(pending(&queue), ["C", "A", "B"]);
+
+        queue.remove(3);
+        queue.remove(1);
+        assert_eq!(pending(&queue), ["C", "B"]);
+    }
+}
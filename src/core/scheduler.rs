@@ -0,0 +1,394 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Una ejecución detectada con más retraso que esto se considera perdida
+const MISSED_TOLERANCE_MINUTES: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Repeat {
+    #[default]
+    Once,
+    Daily,
+    Weekly,
+}
+
+impl Repeat {
+    pub const ALL: [Repeat; 3] = [Repeat::Once, Repeat::Daily, Repeat::Weekly];
+
+    pub fn lang_key(&self) -> &'static str {
+        match self {
+            Repeat::Once => "repeat_once",
+            Repeat::Daily => "repeat_daily",
+            Repeat::Weekly => "repeat_weekly",
+        }
+    }
+}
+
+/// Qué hacer con una ejecución que ocurrió con la app cerrada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedRunPolicy {
+    #[default]
+    Skip,
+    RunOnStart,
+}
+
+impl MissedRunPolicy {
+    pub const ALL: [MissedRunPolicy; 2] = [MissedRunPolicy::Skip, MissedRunPolicy::RunOnStart];
+
+    pub fn lang_key(&self) -> &'static str {
+        match self {
+            MissedRunPolicy::Skip => "missed_skip",
+            MissedRunPolicy::RunOnStart => "missed_run_on_start",
+        }
+    }
+}
+
+/// Simulación programada guardada en la configuración
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledRun {
+    pub id: u64,
+    pub preset: String,
+    pub duration_minutes: u64,
+    pub repeat: Repeat,
+    /// Fecha de la ejecución única
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Día de la ejecución semanal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekday: Option<Weekday>,
+    pub time: NaiveTime,
+    #[serde(default)]
+    pub missed: MissedRunPolicy,
+    /// Última ejecución atendida; al crearla es la hora de creación
+    pub last_run: DateTime<Local>,
+}
+
+/// Resultado de revisar una programación
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleDecision {
+    Run,
+    Skip,
+}
+
+fn local(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    date.and_time(time).and_local_timezone(Local).earliest()
+}
+
+impl ScheduledRun {
+    /// Fecha de la primera ejecución candidata en o antes de `date`
+    fn candidate_on_or_before(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self.repeat {
+            Repeat::Once => self.date.filter(|once| *once <= date),
+            Repeat::Daily => Some(date),
+            Repeat::Weekly => {
+                let weekday = self.weekday?;
+                let back = (date.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                Some(date - Duration::days(back as i64))
+            }
+        }
+    }
+
+    /// Última ejecución que debía ocurrir en o antes de `now`
+    pub fn last_occurrence(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let today = now.date_naive();
+        let date = self.candidate_on_or_before(today)?;
+        let occurrence = local(date, self.time)?;
+        if occurrence <= now {
+            return Some(occurrence);
+        }
+
+        // Hoy todavía no llegó la hora: la anterior fue un período antes
+        let previous = match self.repeat {
+            Repeat::Once => return None,
+            Repeat::Daily => date - Duration::days(1),
+            Repeat::Weekly => date - Duration::days(7),
+        };
+        local(previous, self.time)
+    }
+
+    /// Próxima ejecución después de `now`
+    pub fn next_occurrence(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let today = now.date_naive();
+        let date = match self.repeat {
+            Repeat::Once => self.date?,
+            Repeat::Daily => today,
+            Repeat::Weekly => {
+                let weekday = self.weekday?;
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                today + Duration::days(ahead as i64)
+            }
+        };
+
+        let occurrence = local(date, self.time)?;
+        if occurrence > now && occurrence > self.last_run {
+            return Some(occurrence);
+        }
+
+        match self.repeat {
+            Repeat::Once => None,
+            Repeat::Daily => local(date + Duration::days(1), self.time),
+            Repeat::Weekly => local(date + Duration::days(7), self.time),
+        }
+    }
+
+    /// Indica si hay una ejecución pendiente y si se debe iniciar o saltar
+    pub fn check(&self, now: DateTime<Local>) -> Option<ScheduleDecision> {
+        let occurrence = self.last_occurrence(now)?;
+        if occurrence <= self.last_run {
+            return None;
+        }
+
+        let late = now - occurrence > Duration::minutes(MISSED_TOLERANCE_MINUTES);
+        if late && self.missed == MissedRunPolicy::Skip {
+            Some(ScheduleDecision::Skip)
+        } else {
+            Some(ScheduleDecision::Run)
+        }
+    }
+
+    /// Una programación única que ya se ejecutó no vuelve a ocurrir
+    pub fn is_finished(&self) -> bool {
+        self.repeat == Repeat::Once && self.next_occurrence(self.last_run).is_none()
+    }
+}
+
+pub fn next_schedule_id(schedules: &[ScheduledRun]) -> u64 {
+    schedules.iter().map(|s| s.id).max().unwrap_or(0) + 1
+}
+
+/// Programaciones con su próxima ejecución, de la más cercana a la más lejana
+pub fn upcoming_runs(
+    schedules: &[ScheduledRun],
+    now: DateTime<Local>,
+) -> Vec<(&ScheduledRun, DateTime<Local>)> {
+    let mut upcoming: Vec<_> = schedules
+        .iter()
+        .filter_map(|schedule| Some((schedule, schedule.next_occurrence(now)?)))
+        .collect();
+    upcoming.sort_by_key(|(_, when)| *when);
+    upcoming
+}
+
+/// Revisa las programaciones y devuelve los presets a iniciar como
+/// `(nombre, duración)`. Actualiza `last_run` de las que se atendieron.
+pub fn take_due_runs(schedules: &mut [ScheduledRun], now: DateTime<Local>) -> Vec<(String, u64)> {
+    let mut due = Vec::new();
+
+    for schedule in schedules.iter_mut() {
+        let Some(decision) = schedule.check(now) else {
+            continue;
+        };
+
+        match decision {
+            ScheduleDecision::Run => {
+                log::info!("Scheduled run {} due: {}", schedule.id, schedule.preset);
+                due.push((schedule.preset.clone(), schedule.duration_minutes));
+            }
+            ScheduleDecision::Skip => {
+                log::info!("Skipping missed scheduled run {}: {}", schedule.id, schedule.preset);
+            }
+        }
+        schedule.last_run = now;
+    }
+
+    due
+}
+
+/// Copia a `schedules` los `last_run` más recientes de `saved`, por
+/// ejemplo los que guardó otro proceso en config.json
+pub fn merge_last_runs(schedules: &mut [ScheduledRun], saved: &[ScheduledRun]) {
+    for schedule in schedules.iter_mut() {
+        if let Some(saved) = saved.iter().find(|saved| saved.id == schedule.id) {
+            schedule.last_run = schedule.last_run.max(saved.last_run);
+        }
+    }
+}
+
+const SCHEDULER_LOCK_FILE: &str = "scheduler.lock";
+/// Cuánto espera `scheduler run` a que la interfaz termine su revisión
+const LOCK_WAIT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Lock de las programaciones. `scheduler run` lo toma exclusivo mientras
+/// corre; la interfaz lo toma compartido durante cada revisión, así que
+/// nunca atienden la misma ejecución los dos. El sistema lo libera aunque
+/// el proceso termine de golpe.
+pub struct SchedulerLock {
+    _file: std::fs::File,
+}
+
+impl SchedulerLock {
+    fn open() -> std::io::Result<std::fs::File> {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(SCHEDULER_LOCK_FILE)
+    }
+
+    /// Lock exclusivo de `scheduler run`. Espera un momento por si la
+    /// interfaz está revisando; devuelve `None` si otro proceso lo tiene.
+    pub fn acquire() -> std::io::Result<Option<Self>> {
+        let file = Self::open()?;
+        let deadline = std::time::Instant::now() + LOCK_WAIT;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Some(Self { _file: file })),
+                Err(std::fs::TryLockError::WouldBlock) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+                Err(std::fs::TryLockError::WouldBlock) => return Ok(None),
+                Err(std::fs::TryLockError::Error(e)) => return Err(e),
+            }
+        }
+    }
+
+    /// Lock compartido de la interfaz para una revisión. Devuelve `None`
+    /// si hay un `scheduler run` en curso.
+    pub fn acquire_shared() -> std::io::Result<Option<Self>> {
+        let file = Self::open()?;
+        match file.try_lock_shared() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(std::fs::TryLockError::WouldBlock) => Ok(None),
+            Err(std::fs::TryLockError::Error(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::in_temp_dir;
+
+    fn daily(id: u64, last_run: DateTime<Local>) -> ScheduledRun {
+        ScheduledRun {
+            id,
+            preset: "Valorant".to_string(),
+            duration_minutes: 15,
+            repeat: Repeat::Daily,
+            date: None,
+            weekday: None,
+            time: NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+            missed: MissedRunPolicy::Skip,
+            last_run,
+        }
+    }
+
+    /// Hora local fija; enero evita los cambios de horario
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        local(
+            NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            NaiveTime::from_hms_opt(hour, minute, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn daily_run_is_due_at_its_time() {
+        let schedule = daily(1, at(2026, 1, 4, 12, 0));
+
+        assert_eq!(schedule.check(at(2026, 1, 5, 9, 59)), None);
+        assert_eq!(schedule.last_occurrence(at(2026, 1, 5, 9, 59)), Some(at(2026, 1, 4, 10, 0)));
+        assert_eq!(schedule.check(at(2026, 1, 5, 10, 1)), Some(ScheduleDecision::Run));
+        assert_eq!(schedule.next_occurrence(at(2026, 1, 5, 10, 1)), Some(at(2026, 1, 6, 10, 0)));
+    }
+
+    #[test]
+    fn missed_daily_run_is_skipped() {
+        // La app estuvo cerrada el 4 a las 10:00
+        let mut schedules = vec![daily(1, at(2026, 1, 3, 12, 0))];
+        let now = at(2026, 1, 5, 9, 0);
+
+        assert_eq!(schedules[0].check(now), Some(ScheduleDecision::Skip));
+        assert!(take_due_runs(&mut schedules, now).is_empty());
+        assert_eq!(schedules[0].last_run, now);
+        assert_eq!(schedules[0].check(now), None);
+    }
+
+    #[test]
+    fn missed_daily_run_runs_once_on_start() {
+        let mut schedule = daily(1, at(2026, 1, 1, 12, 0));
+        schedule.missed = MissedRunPolicy::RunOnStart;
+        let mut schedules = vec![schedule];
+        let now = at(2026, 1, 5, 9, 0);
+
+        // Varias ejecuciones perdidas se recuperan con una sola
+        assert_eq!(schedules[0].check(now), Some(ScheduleDecision::Run));
+        assert_eq!(take_due_runs(&mut schedules, now), vec![("Valorant".to_string(), 15)]);
+        assert!(take_due_runs(&mut schedules, at(2026, 1, 5, 9, 5)).is_empty());
+        assert_eq!(schedules[0].check(at(2026, 1, 5, 10, 0)), Some(ScheduleDecision::Run));
+    }
+
+    #[test]
+    fn weekly_run_crosses_week_and_year() {
+        let mut schedule = daily(1, at(2025, 12, 1, 12, 0));
+        schedule.repeat = Repeat::Weekly;
+        schedule.weekday = Some(Weekday::Mon);
+
+        // Domingo 4 de enero de 2026: la anterior fue el lunes 29 de diciembre
+        let sunday = at(2026, 1, 4, 18, 0);
+        assert_eq!(schedule.last_occurrence(sunday), Some(at(2025, 12, 29, 10, 0)));
+        assert_eq!(schedule.next_occurrence(sunday), Some(at(2026, 1, 5, 10, 0)));
+
+        // El mismo lunes, antes y después de la hora
+        assert_eq!(schedule.last_occurrence(at(2026, 1, 5, 9, 0)), Some(at(2025, 12, 29, 10, 0)));
+        assert_eq!(schedule.last_occurrence(at(2026, 1, 5, 10, 0)), Some(at(2026, 1, 5, 10, 0)));
+        assert_eq!(schedule.next_occurrence(at(2026, 1, 5, 10, 0)), Some(at(2026, 1, 12, 10, 0)));
+    }
+
+    #[test]
+    fn once_run_finishes_after_running() {
+        let mut schedule = daily(1, at(2026, 1, 1, 12, 0));
+        schedule.repeat = Repeat::Once;
+        schedule.date = NaiveDate::from_ymd_opt(2026, 1, 5);
+        let mut schedules = vec![schedule];
+
+        assert_eq!(schedules[0].last_occurrence(at(2026, 1, 4, 10, 0)), None);
+        assert_eq!(schedules[0].next_occurrence(at(2026, 1, 4, 10, 0)), Some(at(2026, 1, 5, 10, 0)));
+        assert!(!schedules[0].is_finished());
+
+        let now = at(2026, 1, 5, 10, 1);
+        assert_eq!(take_due_runs(&mut schedules, now).len(), 1);
+        assert!(schedules[0].is_finished());
+        assert_eq!(schedules[0].next_occurrence(now), None);
+        assert_eq!(schedules[0].check(at(2026, 1, 6, 10, 0)), None);
+    }
+
+    #[test]
+    fn merge_keeps_latest_run() {
+        let now = Local::now();
+        let earlier = now - Duration::days(1);
+        let mut schedules = vec![daily(1, earlier), daily(2, now)];
+
+        merge_last_runs(&mut schedules, &[daily(1, now), daily(2, earlier), daily(3, now)]);
+
+        assert_eq!(schedules.len(), 2);
+        assert_eq!(schedules[0].last_run, now);
+        assert_eq!(schedules[1].last_run, now);
+    }
+
+    #[test]
+    fn scheduler_lock_excludes_the_app() {
+        in_temp_dir(|_| {
+            let lock = SchedulerLock::acquire().unwrap().expect("first lock");
+            assert!(SchedulerLock::acquire_shared().unwrap().is_none());
+            drop(lock);
+
+            // Una revisión de la interfaz solo demora a `scheduler run`
+            let shared = SchedulerLock::acquire_shared().unwrap().expect("shared lock");
+            let release = std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                drop(shared);
+            });
+            let lock = SchedulerLock::acquire().unwrap().expect("lock after the check");
+            release.join().unwrap();
+            assert!(SchedulerLock::acquire_shared().unwrap().is_none());
+            drop(lock);
+        });
+    }
+}
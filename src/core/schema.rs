@@ -0,0 +1,371 @@
+use crate::shared::types::Preset;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::fmt;
+
+/// Versión actual del formato de archivos de presets.
+/// La versión 0 es el formato antiguo: un array de presets sin envoltorio.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Migración de una entrada desde la versión `N` a la `N + 1`
+type Migration = fn(Value) -> Value;
+
+/// `MIGRATIONS[n]` migra una entrada de la versión `n` a la `n + 1`
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1, migrate_v1_to_v2];
+
+/// La v1 solo agrega el envoltorio del documento; las entradas no cambian
+fn migrate_v0_to_v1(entry: Value) -> Value {
+    entry
+}
+
+/// La v2 reemplaza `executable` y `path` por la lista `executables`
+fn migrate_v1_to_v2(mut entry: Value) -> Value {
+    if let Some(object) = entry.as_object_mut() {
+        if !object.contains_key("executables") {
+            let mut executable = serde_json::Map::new();
+            for key in ["executable", "path"] {
+                if let Some(value) = object.remove(key) {
+                    executable.insert(key.to_string(), value);
+                }
+            }
+            object.insert(
+                "executables".to_string(),
+                Value::Array(vec![Value::Object(executable)]),
+            );
+        }
+    }
+    entry
+}
+
+#[derive(Deserialize)]
+struct RawDocument<'a> {
+    schema_version: u32,
+    #[serde(borrow)]
+    presets: Vec<&'a RawValue>,
+}
+
+/// Entrada del formato v0 que leen las versiones anteriores de la app
+#[derive(Serialize)]
+struct LegacyEntry<'a> {
+    name: &'a str,
+    executable: &'a str,
+    path: &'a str,
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    schema_version: u32,
+    presets: &'a [Preset],
+}
+
+/// Error al interpretar un archivo o una entrada de presets
+#[derive(Debug, Clone)]
+pub struct PresetParseError {
+    pub file: String,
+    pub entry: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PresetParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)?;
+        if let Some(entry) = self.entry {
+            write!(f, " (entry {})", entry)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for PresetParseError {}
+
+/// Resultado de interpretar un archivo de presets
+#[derive(Debug, Default)]
+pub struct ParsedPresets {
+    pub schema_version: u32,
+    pub presets: Vec<Preset>,
+    pub errors: Vec<PresetParseError>,
+}
+
+/// Calcula línea y columna (desde 1) de un desplazamiento en bytes
+fn position(data: &str, offset: usize) -> (usize, usize) {
+    let before = &data[..offset.min(data.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|l| l.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
+}
+
+/// Interpreta un archivo de presets de cualquier versión soportada.
+///
+/// Los errores de sintaxis del documento se devuelven como `Err`. Las
+/// entradas individuales inválidas se omiten y se informan en `errors`.
+pub fn parse_presets(file: &str, data: &str) -> Result<ParsedPresets, PresetParseError> {
+    let document_error = |e: serde_json::Error| PresetParseError {
+        file: file.to_string(),
+        entry: None,
+        line: e.line(),
+        column: e.column(),
+        message: e.to_string(),
+    };
+
+    let (schema_version, entries) = if data.trim_start().starts_with('[') {
+        let entries: Vec<&RawValue> = serde_json::from_str(data).map_err(document_error)?;
+        (0, entries)
+    } else {
+        let document: RawDocument = serde_json::from_str(data).map_err(document_error)?;
+        (document.schema_version, document.presets)
+    };
+
+    if schema_version > CURRENT_SCHEMA_VERSION {
+        return Err(PresetParseError {
+            file: file.to_string(),
+            entry: None,
+            line: 1,
+            column: 1,
+            message: format!(
+                "schema_version {} is newer than the supported version {}",
+                schema_version, CURRENT_SCHEMA_VERSION
+            ),
+        });
+    }
+
+    let mut parsed = ParsedPresets {
+        schema_version,
+        ..Default::default()
+    };
+
+    for (index, raw) in entries.into_iter().enumerate() {
+        // RawValue apunta dentro de `data`, así que se puede ubicar la entrada
+        let offset = raw.get().as_ptr() as usize - data.as_ptr() as usize;
+        let (line, column) = position(data, offset);
+
+        match parse_entry(raw.get(), schema_version) {
+            Ok(preset) => parsed.presets.push(preset),
+            Err(e) => {
+                // Si el error tiene posición dentro de la entrada, hacerla absoluta
+                let relative = e.line() > 0 && schema_version == CURRENT_SCHEMA_VERSION;
+                let (line, column) = if relative {
+                    if e.line() == 1 {
+                        (line, column + e.column().saturating_sub(1))
+                    } else {
+                        (line + e.line() - 1, e.column())
+                    }
+                } else {
+                    (line, column)
+                };
+
+                let message = strip_position(&e.to_string());
+                log::warn!(
+                    "Skipping invalid preset in {} at {}:{}: {}",
+                    file,
+                    line,
+                    column,
+                    message
+                );
+                parsed.errors.push(PresetParseError {
+                    file: file.to_string(),
+                    entry: Some(index),
+                    line,
+                    column,
+                    message,
+                });
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Quita el sufijo " at line X column Y" que es relativo a la entrada
+fn strip_position(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string(),
+    }
+}
+
+/// Interpreta una entrada aplicando las migraciones necesarias
+fn parse_entry(raw: &str, schema_version: u32) -> Result<Preset, serde_json::Error> {
+    if schema_version == CURRENT_SCHEMA_VERSION {
+        return serde_json::from_str(raw);
+    }
+
+    let mut value: Value = serde_json::from_str(raw)?;
+    for migration in &MIGRATIONS[schema_version as usize..] {
+        value = migration(value);
+    }
+    serde_json::from_value(value)
+}
+
+/// Serializa los presets en el formato de documento actual
+pub fn serialize_presets(presets: &[Preset]) -> Result<String, serde_json::Error> {
+    let mut json = serde_json::to_string_pretty(&Document {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        presets,
+    })?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Serializa los presets en el formato v0 (array sin envoltorio) para las
+/// versiones que no leen documentos. Solo se conserva el ejecutable principal.
+pub fn serialize_legacy_presets(presets: &[Preset]) -> Result<String, serde_json::Error> {
+    let entries: Vec<LegacyEntry> = presets
+        .iter()
+        .filter_map(|preset| {
+            let primary = preset.primary()?;
+            Some(LegacyEntry {
+                name: &preset.name,
+                executable: &primary.executable,
+                path: &primary.path,
+            })
+        })
+        .collect();
+    let mut json = serde_json::to_string_pretty(&entries)?;
+    json.push('\n');
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"{
+  "schema_version": 2,
+  "presets": [
+    { "name": "Good", "executables": [{ "executable": "a.exe", "path": "A" }] },
+    { "name": "Short", "executables": [], "duration_minutes": "ten" },
+    {
+      "name": "Bad",
+      "executables": [{ "executable": "b.exe", "path": "B", "role": "boss" }]
+    }
+  ]
+}"#;
+
+    /// Línea y columna (desde 1) del último carácter de `text`, donde
+    /// serde informa los errores de un valor
+    fn last_char_of(data: &str, text: &str) -> (usize, usize) {
+        let offset = data.find(text).unwrap() + text.len() - 1;
+        position(data, offset)
+    }
+
+    #[test]
+    fn invalid_entries_are_skipped() {
+        let parsed = parse_presets("presets.json", DOCUMENT).unwrap();
+
+        assert_eq!(parsed.schema_version, 2);
+        let names: Vec<&str> = parsed.presets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Good"]);
+        let entries: Vec<Option<usize>> = parsed.errors.iter().map(|e| e.entry).collect();
+        assert_eq!(entries, vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn errors_point_into_the_file() {
+        let parsed = parse_presets("presets.json", DOCUMENT).unwrap();
+
+        // Error en la primera línea de la entrada
+        let short = &parsed.errors[0];
+        assert_eq!((short.line, short.column), last_char_of(DOCUMENT, r#""ten""#));
+        assert_eq!((short.line, short.column), (5, 67));
+        assert_eq!(short.message, r#"invalid type: string "ten", expected u64"#);
+
+        // Error en una línea posterior de la entrada
+        let bad = &parsed.errors[1];
+        assert_eq!((bad.line, bad.column), last_char_of(DOCUMENT, r#""boss""#));
+        assert_eq!(bad.line, 8);
+        assert!(bad.message.starts_with("unknown variant `boss`"));
+        assert_eq!(
+            bad.to_string(),
+            format!("presets.json:8:{} (entry 2): {}", bad.column, bad.message)
+        );
+    }
+
+    #[test]
+    fn syntax_errors_reject_the_document() {
+        let data = "{\n  \"schema_version\": 2,\n  \"presets\": [\n}";
+        let error = parse_presets("presets.json", data).unwrap_err();
+        assert_eq!(error.entry, None);
+        assert_eq!(error.line, 4);
+    }
+
+    #[test]
+    fn old_versions_are_migrated() {
+        let v0 = r#"[{ "name": "Old", "executable": "old.exe", "path": "Old" }]"#;
+        let v1 = r#"{
+  "schema_version": 1,
+  "presets": [{ "name": "Old", "executable": "old.exe", "path": "Old", "tags": ["rpg"] }]
+}"#;
+
+        for (data, version) in [(v0, 0), (v1, 1)] {
+            let parsed = parse_presets("presets.json", data).unwrap();
+            assert_eq!(parsed.schema_version, version);
+            assert!(parsed.errors.is_empty());
+            let preset = &parsed.presets[0];
+            assert_eq!(preset.executables.len(), 1);
+            assert_eq!(preset.executables[0].executable, "old.exe");
+            assert_eq!(preset.executables[0].path, "Old");
+        }
+
+        // Guardar siempre escribe la versión actual
+        let parsed = parse_presets("presets.json", v1).unwrap();
+        let saved = serialize_presets(&parsed.presets).unwrap();
+        let reparsed = parse_presets("presets.json", &saved).unwrap();
+        assert_eq!(reparsed.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(reparsed.presets[0].tags, vec!["rpg".to_string()]);
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let data = r#"{ "schema_version": 3, "presets": [] }"#;
+        let error = parse_presets("presets.json", data).unwrap_err();
+        assert_eq!(
+            error.message,
+            format!(
+                "schema_version 3 is newer than the supported version {}",
+                CURRENT_SCHEMA_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn legacy_output_keeps_primary_executable() {
+        let document = r#"{
+  "schema_version": 2,
+  "presets": [
+    {
+      "name": "Game",
+      "executables": [
+        { "executable": "launcher.exe", "path": "Game", "role": "launcher" },
+        { "executable": "game.exe", "path": "Game/bin" }
+      ],
+      "tags": ["rpg"]
+    },
+    { "name": "Empty", "executables": [] }
+  ]
+}"#;
+        let parsed = parse_presets("presets.json", document).unwrap();
+        let legacy = serialize_legacy_presets(&parsed.presets).unwrap();
+
+        let entries: Vec<Value> = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(
+            entries,
+            vec![serde_json::json!({
+                "name": "Game",
+                "executable": "game.exe",
+                "path": "Game/bin"
+            })]
+        );
+
+        let reparsed = parse_presets("presets.json", &legacy).unwrap();
+        assert_eq!(reparsed.schema_version, 0);
+        assert_eq!(reparsed.presets[0].primary().unwrap().executable, "game.exe");
+    }
+}
@@ -1,16 +1,22 @@
 #![windows_subsystem = "windows"]
 
 mod app;
+mod cli;
 mod core;
 mod platform;
 mod shared;
+#[cfg(test)]
+mod test_support;
 
+use app::api::start_server;
 use app::state::DsqApp;
-use core::presets::{is_presets_outdated, load_presets};
+use core::history::load_history;
+use core::presets::is_presets_outdated;
+use core::quests::load_quests;
 use eframe::egui;
 use shared::config::load_config;
 use shared::lang::load_language;
-use shared::richpresence::RichPresenceManager;
+use shared::types::Config;
 
 fn main() -> Result<(), eframe::Error> {
     let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(
@@ -22,6 +28,10 @@ fn main() -> Result<(), eframe::Error> {
     ))
     .try_init();
 
+    if let Some(code) = cli::run_from_args() {
+        std::process::exit(code);
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([505.0, 500.0])
@@ -29,30 +39,56 @@ fn main() -> Result<(), eframe::Error> {
         ..Default::default()
     };
 
-    let config = load_config();
     let mut app = DsqApp::default();
+    let config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            app.config_load_error = Some(e);
+            Config::new()
+        }
+    };
 
-    app.presets = load_presets();
-    app.filtered_presets = app.presets.clone();
     app.presets_outdated = is_presets_outdated();
     app.langs.insert("Español".to_string(), load_language("es"));
     app.langs.insert("English".to_string(), load_language("en"));
 
+    // Lo que ya está en disco no hace falta volver a guardarlo
+    app.saved_config = serde_json::to_string(&config).unwrap_or_default();
     app.selected_lang = config.language;
     app.selected_preset = config.selected_preset;
+    app.reload_presets();
     app.process_name = config.process_name;
     app.custom_path = config.custom_path;
     app.rich_presence_enabled = config.rich_presence_enabled;
+    app.default_duration_minutes = config.default_duration_minutes.max(1);
+    app.duration_minutes = app.default_duration_minutes;
+    app.new_quest_minutes = app.default_duration_minutes;
+    app.new_schedule_minutes = app.default_duration_minutes;
+    app.schedules = config.schedules;
+    app.auto_start = config.auto_start;
+    app.launch_start_preset = config.launch_start_preset;
+    app.last_used_preset = config.last_used_preset;
+    app.api = config.api;
+    app.rich_presence_template = config.rich_presence_template;
+    app.client_id_input = config.rich_presence_client_id.clone();
+    app.rich_presence_client_id = config.rich_presence_client_id;
+    app.process_monitor.set_hooks(config.hooks.clone());
+    app.hooks = config.hooks;
+    app.custom_clients = config.custom_clients;
 
-    // Inicializar Rich Presence si está habilitado
-    if app.rich_presence_enabled {
-        let mut rp = RichPresenceManager::new();
-        if let Ok(()) = rp.connect() {
-            let _ = rp.set_activity(None);
-            app.rich_presence = Some(rp);
+    match load_quests() {
+        Ok(quests) => app.quests = quests,
+        Err(e) => {
+            log::error!("Failed to load quests: {}", e);
+            app.quests_load_error = Some(e.to_string());
         }
     }
 
+    match load_history() {
+        Ok(history) => app.session_history = history,
+        Err(e) => log::error!("Failed to load session history: {}", e),
+    }
+
     eframe::run_native(
         "DSQProcess",
         options,
@@ -62,6 +98,14 @@ fn main() -> Result<(), eframe::Error> {
             style.animation_time = 0.1; // Reducir tiempo de animaciones
             cc.egui_ctx.set_style(style);
 
+            let mut app = app;
+            if app.api.enabled {
+                match start_server(&app.api, cc.egui_ctx.clone()) {
+                    Ok(receiver) => app.api_requests = Some(receiver),
+                    Err(e) => log::error!("Failed to start control API: {}", e),
+                }
+            }
+
             Box::new(app)
         }),
     )
@@ -0,0 +1,277 @@
+//! Registro de clientes de Discord: los oficiales, los de terceros
+//! conocidos y los que agrega el usuario.
+
+use super::discord::{InstallKind, LaunchCommand};
+use crate::shared::types::CustomClient;
+
+/// Cliente conocido y dónde suele instalarse
+#[derive(Debug, PartialEq)]
+pub struct BuiltinClient {
+    pub name: &'static str,
+    pub icon: &'static str,
+    pub process_names: &'static [&'static str],
+    /// Carpeta en `%LOCALAPPDATA%` con `Update.exe` y el ejecutable que abre
+    pub squirrel: Option<(&'static str, &'static str)>,
+    /// Ejecutables en Windows, relativos a `%LOCALAPPDATA%`
+    pub windows_executables: &'static [&'static str],
+    /// Ejecutables en Linux; `~/` es la carpeta personal. Los de
+    /// `/usr/bin` van primero para que, si enlazan a `/opt`, se
+    /// muestren como paquete.
+    pub linux_executables: &'static [(InstallKind, &'static str)],
+    pub flatpak_id: Option<&'static str>,
+    pub snap: Option<&'static str>,
+}
+
+pub const BUILTIN_CLIENTS: &[BuiltinClient] = &[
+    BuiltinClient {
+        name: "Discord",
+        icon: "🍱",
+        process_names: &["Discord"],
+        squirrel: Some(("Discord", "Discord.exe")),
+        windows_executables: &[],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/discord"),
+            (InstallKind::Tarball, "/opt/discord/Discord"),
+            (InstallKind::Tarball, "/opt/Discord/Discord"),
+            (InstallKind::Tarball, "~/.local/share/Discord/Discord"),
+            (InstallKind::Tarball, "~/.local/share/discord/Discord"),
+        ],
+        flatpak_id: Some("com.discordapp.Discord"),
+        snap: Some("discord"),
+    },
+    BuiltinClient {
+        name: "Discord PTB",
+        icon: "🔎",
+        process_names: &["DiscordPTB"],
+        squirrel: Some(("DiscordPTB", "DiscordPTB.exe")),
+        windows_executables: &[],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/discord-ptb"),
+            (InstallKind::Tarball, "/opt/discord-ptb/DiscordPTB"),
+            (InstallKind::Tarball, "/opt/DiscordPTB/DiscordPTB"),
+            (InstallKind::Tarball, "~/.local/share/DiscordPTB/DiscordPTB"),
+        ],
+        flatpak_id: None,
+        snap: None,
+    },
+    BuiltinClient {
+        name: "Discord Canary",
+        icon: "🛠",
+        process_names: &["DiscordCanary"],
+        squirrel: Some(("DiscordCanary", "DiscordCanary.exe")),
+        windows_executables: &[],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/discord-canary"),
+            (InstallKind::Tarball, "/opt/discord-canary/DiscordCanary"),
+            (InstallKind::Tarball, "/opt/DiscordCanary/DiscordCanary"),
+            (InstallKind::Tarball, "~/.local/share/DiscordCanary/DiscordCanary"),
+        ],
+        flatpak_id: Some("com.discordapp.DiscordCanary"),
+        snap: Some("discord-canary"),
+    },
+    BuiltinClient {
+        name: "Vesktop",
+        icon: "🧩",
+        process_names: &["vesktop"],
+        squirrel: None,
+        windows_executables: &["vesktop/Vesktop.exe", "Programs/vesktop/Vesktop.exe"],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/vesktop"),
+            (InstallKind::Tarball, "/opt/Vesktop/vesktop"),
+        ],
+        flatpak_id: Some("dev.vencord.Vesktop"),
+        snap: None,
+    },
+    BuiltinClient {
+        name: "Equibop",
+        icon: "🧩",
+        process_names: &["equibop"],
+        squirrel: None,
+        windows_executables: &["Programs/equibop/Equibop.exe"],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/equibop"),
+            (InstallKind::Tarball, "/opt/Equibop/equibop"),
+        ],
+        flatpak_id: Some("io.github.equicord.equibop"),
+        snap: None,
+    },
+    BuiltinClient {
+        name: "Legcord",
+        icon: "🦵",
+        process_names: &["legcord"],
+        squirrel: None,
+        windows_executables: &["Programs/legcord/Legcord.exe"],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/legcord"),
+            (InstallKind::Tarball, "/opt/Legcord/legcord"),
+        ],
+        flatpak_id: Some("app.legcord.Legcord"),
+        snap: Some("legcord"),
+    },
+    BuiltinClient {
+        name: "ArmCord",
+        icon: "🦾",
+        process_names: &["armcord"],
+        squirrel: None,
+        windows_executables: &["Programs/armcord/ArmCord.exe"],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/armcord"),
+            (InstallKind::Tarball, "/opt/ArmCord/armcord"),
+        ],
+        flatpak_id: Some("xyz.armcord.ArmCord"),
+        snap: Some("armcord"),
+    },
+    BuiltinClient {
+        name: "WebCord",
+        icon: "🕸",
+        process_names: &["webcord"],
+        squirrel: None,
+        windows_executables: &["Programs/webcord/WebCord.exe"],
+        linux_executables: &[
+            (InstallKind::Package, "/usr/bin/webcord"),
+            (InstallKind::Tarball, "/opt/WebCord/webcord"),
+        ],
+        flatpak_id: Some("io.github.spacingbat3.webcord"),
+        snap: None,
+    },
+];
+
+/// Cliente de Discord que se puede detectar y abrir
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscordClient {
+    pub name: String,
+    pub icon: String,
+    pub process_names: Vec<String>,
+    /// Comando del usuario; los conocidos se buscan en `builtin`
+    pub launch: Option<LaunchCommand>,
+    pub builtin: Option<&'static BuiltinClient>,
+}
+
+impl DiscordClient {
+    fn from_builtin(builtin: &'static BuiltinClient) -> Self {
+        Self {
+            name: builtin.name.to_string(),
+            icon: builtin.icon.to_string(),
+            process_names: builtin.process_names.iter().map(|s| s.to_string()).collect(),
+            launch: None,
+            builtin: Some(builtin),
+        }
+    }
+
+    fn from_custom(custom: &CustomClient) -> Option<Self> {
+        let name = custom.name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            icon: "⭐".to_string(),
+            process_names: custom
+                .process_names
+                .iter()
+                .map(|process| process.trim().to_string())
+                .filter(|process| !process.is_empty())
+                .collect(),
+            launch: LaunchCommand::parse(&custom.launch_command),
+            builtin: None,
+        })
+    }
+
+    pub fn matches_process(&self, process_name: &str) -> bool {
+        let process_name = normalize_process_name(process_name);
+        self.process_names
+            .iter()
+            .any(|name| normalize_process_name(name) == process_name)
+    }
+}
+
+/// `Discord.exe`, `discord-canary` y `DiscordCanary` quedan iguales
+pub fn normalize_process_name(name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect()
+}
+
+/// Clientes conocidos más los del usuario
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientRegistry {
+    pub clients: Vec<DiscordClient>,
+}
+
+impl ClientRegistry {
+    /// Los del usuario van primero: si comparten nombre de proceso
+    /// con uno conocido, se prefiere el del usuario
+    pub fn new(custom: &[CustomClient]) -> Self {
+        let clients = custom
+            .iter()
+            .filter_map(DiscordClient::from_custom)
+            .chain(BUILTIN_CLIENTS.iter().map(DiscordClient::from_builtin))
+            .collect();
+        Self { clients }
+    }
+
+    pub fn by_process_name(&self, process_name: &str) -> Option<&DiscordClient> {
+        self.clients
+            .iter()
+            .find(|client| client.matches_process(process_name))
+    }
+
+    /// Cliente según una carpeta de Flatpak (`com.discordapp.Discord`)
+    /// o de Snap (`snap.discord-canary`)
+    pub fn by_sandbox_name(&self, name: &str) -> Option<&DiscordClient> {
+        self.clients.iter().find(|client| {
+            client.builtin.is_some_and(|builtin| {
+                builtin.flatpak_id == Some(name)
+                    || name
+                        .strip_prefix("snap.")
+                        .is_some_and(|snap| builtin.snap == Some(snap))
+            })
+        })
+    }
+}
+
+impl Default for ClientRegistry {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_matches_process_names() {
+        let registry = ClientRegistry::default();
+        let name = |process: &str| registry.by_process_name(process).map(|c| c.name.clone());
+
+        assert_eq!(name("Discord.exe").as_deref(), Some("Discord"));
+        assert_eq!(name("discord").as_deref(), Some("Discord"));
+        assert_eq!(name("DiscordCanary").as_deref(), Some("Discord Canary"));
+        assert_eq!(name("discord-ptb").as_deref(), Some("Discord PTB"));
+        assert_eq!(name("vesktop").as_deref(), Some("Vesktop"));
+        assert_eq!(name("DiscordHelper"), None);
+
+        let sandbox = |dir: &str| registry.by_sandbox_name(dir).map(|c| c.name.clone());
+        assert_eq!(sandbox("dev.vencord.Vesktop").as_deref(), Some("Vesktop"));
+        assert_eq!(sandbox("snap.discord-canary").as_deref(), Some("Discord Canary"));
+    }
+
+    #[test]
+    fn registry_prefers_custom_clients() {
+        let registry = ClientRegistry::new(&[CustomClient {
+            name: "Discord (D:)".to_string(),
+            process_names: vec!["Discord".to_string(), " ".to_string()],
+            launch_command: "\"D:/Apps/Discord/Discord.exe\" --multi-instance".to_string(),
+        }]);
+
+        let client = registry.by_process_name("Discord.exe").unwrap();
+        assert_eq!(client.name, "Discord (D:)");
+        assert_eq!(client.process_names, vec!["Discord".to_string()]);
+        let launch = client.launch.as_ref().unwrap();
+        assert_eq!(launch.program, std::path::Path::new("D:/Apps/Discord/Discord.exe"));
+        assert_eq!(launch.args, vec!["--multi-instance".to_string()]);
+    }
+}
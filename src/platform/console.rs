@@ -0,0 +1,26 @@
+//! Consola para los comandos de línea en Windows.
+//!
+//! El ejecutable usa el subsistema `windows` para que la interfaz no abra
+//! una consola, así que la salida de la CLI no llega a ningún lado hasta
+//! que nos conectamos a la consola del proceso que nos lanzó.
+
+/// Se conecta a la consola del proceso padre, si tiene una
+#[cfg(windows)]
+pub fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // SAFETY: AttachConsole no recibe punteros; si falla (sin consola del
+    // padre o ya conectados) la salida simplemente se descarta como antes
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// En los demás sistemas la salida estándar ya está conectada
+#[cfg(not(windows))]
+pub fn attach_parent_console() {}
@@ -1,73 +1,455 @@
+use super::clients::{ClientRegistry, DiscordClient};
+use crate::shared::process_table::ProcessTable;
 use std::process::Command;
 use std::path::{ Path, PathBuf };
-use std::ffi::OsStr;
-use sysinfo::System;
 
-#[allow(clippy::upper_case_acronyms)]
+/// Cuántos sockets `discord-ipc-N` abre Discord como máximo
+const IPC_SOCKET_COUNT: u8 = 10;
+
+/// Socket IPC de Discord que acepta conexiones
 #[derive(Debug, Clone, PartialEq)]
-pub enum DiscordVersion {
-    Stable,
-    Canary,
-    PTB,
+pub struct IpcSocket {
+    pub index: u8,
+    pub path: PathBuf,
+    /// Cliente que atiende el socket, si se pudo averiguar
+    pub owner: Option<DiscordClient>,
 }
 
-impl DiscordVersion {
-    pub fn exe_name(&self) -> &'static str {
-        match self {
-            DiscordVersion::Stable => "Discord.exe",
-            DiscordVersion::Canary => "DiscordCanary.exe",
-            DiscordVersion::PTB => "DiscordPTB.exe",
+impl IpcSocket {
+    pub fn name(&self) -> String {
+        format!("discord-ipc-{}", self.index)
+    }
+}
+
+/// Resultado de buscar Discord
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiscordDetection {
+    /// En el mismo orden en que los prueba Rich Presence, así que el
+    /// primero es el que usará
+    pub sockets: Vec<IpcSocket>,
+    /// Clientes encontrados por nombre de proceso
+    pub processes: Vec<DiscordClient>,
+}
+
+impl DiscordDetection {
+    pub fn is_running(&self) -> bool {
+        !self.sockets.is_empty() || !self.processes.is_empty()
+    }
+
+    /// Socket al que se conectará Rich Presence
+    pub fn rich_presence_socket(&self) -> Option<&IpcSocket> {
+        self.sockets.first()
+    }
+}
+
+/// Busca Discord probando primero sus sockets IPC. Solo recorre los
+/// procesos si no hay sockets o si falta saber de quién es alguno.
+pub fn detect_discord(registry: &ClientRegistry, table: &ProcessTable) -> DiscordDetection {
+    let mut detection = DiscordDetection {
+        sockets: find_ipc_sockets(registry, table),
+        processes: Vec::new(),
+    };
+
+    if detection.sockets.is_empty() || detection.sockets.iter().any(|s| s.owner.is_none()) {
+        detection.processes = find_discord_processes(registry, table);
+
+        // Con un solo cliente abierto, los sockets tienen que ser suyos
+        if let [only] = detection.processes.as_slice() {
+            for socket in detection.sockets.iter_mut().filter(|s| s.owner.is_none()) {
+                socket.owner = Some(only.clone());
+            }
         }
     }
 
-    pub fn folder_name(&self) -> &'static str {
-        match self {
-            DiscordVersion::Stable => "Discord",
-            DiscordVersion::Canary => "DiscordCanary",
-            DiscordVersion::PTB => "DiscordPTB",
+    detection
+}
+
+/// Busca los clientes de Discord por nombre de proceso
+fn find_discord_processes(registry: &ClientRegistry, table: &ProcessTable) -> Vec<DiscordClient> {
+    table.refresh_all();
+
+    let mut found = vec![];
+    for name in table.names() {
+        if let Some(client) = registry.by_process_name(&name) {
+            if !found.contains(client) {
+                found.push(client.clone());
+            }
         }
     }
+    found
 }
 
-pub fn is_discord_running() -> bool {
-    let system = System::new_all();
+/// Nombre del proceso con ese PID, sin recorrer el resto
+#[cfg(any(target_os = "linux", windows))]
+fn process_owner(pid: u32, registry: &ClientRegistry, table: &ProcessTable) -> Option<DiscordClient> {
+    table.refresh_pids(&[pid]);
+    let name = table.name(pid)?;
+    registry.by_process_name(&name).cloned()
+}
+
+/// Carpetas donde Discord crea los sockets, las mismas que prueba
+/// `discord-rich-presence`
+#[cfg(unix)]
+const IPC_SUBPATHS: [&str; 7] = [
+    "",
+    "app/com.discordapp.Discord",
+    "app/dev.vencord.Vesktop",
+    ".flatpak/com.discordapp.Discord/xdg-run",
+    ".flatpak/dev.vencord.Vesktop/xdg-run",
+    "snap.discord-canary",
+    "snap.discord",
+];
+
+#[cfg(unix)]
+fn ipc_base_directory() -> PathBuf {
+    ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+}
+
+/// Cliente que se deduce de la carpeta de Flatpak o Snap
+#[cfg(unix)]
+fn owner_from_subpath(subpath: &str, registry: &ClientRegistry) -> Option<DiscordClient> {
+    Path::new(subpath).components().find_map(|component| {
+        registry
+            .by_sandbox_name(component.as_os_str().to_str()?)
+            .cloned()
+    })
+}
+
+/// Resultado de conectarse a un socket, válido mientras el archivo sea
+/// el mismo: Discord crea uno nuevo cada vez que abre
+#[cfg(unix)]
+#[derive(Clone, Copy)]
+struct ProbedSocket {
+    /// Dispositivo, inodo y fecha de creación del archivo
+    file: (u64, u64, i64, i64),
+    listening: bool,
+    /// Proceso que escucha, si el sistema lo informa
+    pid: Option<u32>,
+}
+
+#[cfg(unix)]
+static PROBED_SOCKETS: std::sync::Mutex<std::collections::BTreeMap<PathBuf, ProbedSocket>> =
+    std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+/// Indica si el socket acepta conexiones y quién escucha en él. Solo se
+/// conecta si el archivo cambió desde la última vez o si el proceso que
+/// escuchaba ya no existe.
+#[cfg(unix)]
+fn probe_socket(
+    path: &Path,
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))] table: &ProcessTable,
+) -> Option<Option<u32>> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    use std::os::unix::net::UnixStream;
+
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.file_type().is_socket() {
+        return None;
+    }
+    let file = (metadata.dev(), metadata.ino(), metadata.ctime(), metadata.ctime_nsec());
+
+    let mut probed_sockets = PROBED_SOCKETS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(probed) = probed_sockets.get(path).filter(|probed| probed.file == file) {
+        #[cfg(target_os = "linux")]
+        let alive = probed.pid.is_none_or(|pid| {
+            table.refresh_pids(&[pid]);
+            table.name(pid).is_some()
+        });
+        #[cfg(not(target_os = "linux"))]
+        let alive = true;
 
-    if system.processes_by_name(OsStr::new("Discord")).next().is_some() {
-        return true;
+        if alive {
+            return probed.listening.then_some(probed.pid);
+        }
     }
 
-    if system.processes_by_name(OsStr::new("DiscordCanary")).next().is_some() {
-        return true;
+    // Un archivo que no acepta conexiones quedó de un Discord cerrado
+    let stream = UnixStream::connect(path);
+    if stream.is_err() {
+        log::debug!("Ignoring stale IPC socket {}", path.display());
     }
+    #[cfg(target_os = "linux")]
+    let pid = stream.as_ref().ok().and_then(peer_pid);
+    #[cfg(not(target_os = "linux"))]
+    let pid = None;
+
+    let probed = ProbedSocket {
+        file,
+        listening: stream.is_ok(),
+        pid,
+    };
+    probed_sockets.insert(path.to_path_buf(), probed);
+    probed.listening.then_some(probed.pid)
+}
+
+/// Prueba los sockets en el mismo orden que Rich Presence: primero
+/// el número y después la carpeta
+#[cfg(unix)]
+pub fn find_ipc_sockets(registry: &ClientRegistry, table: &ProcessTable) -> Vec<IpcSocket> {
+    let base = ipc_base_directory();
+    let mut sockets = vec![];
+    for index in 0..IPC_SOCKET_COUNT {
+        for subpath in IPC_SUBPATHS {
+            let path = base.join(subpath).join(format!("discord-ipc-{}", index));
+            let Some(pid) = probe_socket(&path, table) else {
+                continue;
+            };
 
-    if system.processes_by_name(OsStr::new("DiscordPTB")).next().is_some() {
-        return true;
+            #[cfg(target_os = "linux")]
+            let owner = pid.and_then(|pid| process_owner(pid, registry, table));
+            #[cfg(not(target_os = "linux"))]
+            let owner = {
+                let _ = pid;
+                None
+            };
+
+            sockets.push(IpcSocket {
+                index,
+                owner: owner.or_else(|| owner_from_subpath(subpath, registry)),
+                path,
+            });
+        }
     }
+    sockets
+}
 
-    false
+/// PID del proceso que escucha en el otro extremo del socket
+#[cfg(target_os = "linux")]
+fn peer_pid(stream: &std::os::unix::net::UnixStream) -> Option<u32> {
+    use std::os::fd::AsRawFd;
+
+    let mut credentials = libc::ucred { pid: 0, uid: 0, gid: 0 };
+    let mut length = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    // SAFETY: `credentials` y `length` son válidos durante la llamada y
+    // `length` indica el tamaño real del buffer
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut credentials as *mut libc::ucred as *mut libc::c_void,
+            &mut length,
+        )
+    };
+    (result == 0 && credentials.pid > 0).then_some(credentials.pid as u32)
 }
 
-pub fn get_installed_discord_versions() -> Vec<DiscordVersion> {
-    let mut found = vec![];
-    if let Some(local_appdata) = std::env::var_os("LOCALAPPDATA") {
-        for version in &[DiscordVersion::Stable, DiscordVersion::Canary, DiscordVersion::PTB] {
-            let path = Path::new(&local_appdata).join(version.folder_name()).join("Update.exe");
-            if path.exists() {
-                found.push(version.clone());
+/// En Windows los sockets son named pipes. El dueño se pide con una
+/// conexión breve: ocupa una instancia del pipe solo durante la consulta.
+#[cfg(windows)]
+pub fn find_ipc_sockets(registry: &ClientRegistry, table: &ProcessTable) -> Vec<IpcSocket> {
+    let Ok(entries) = std::fs::read_dir(r"\\.\pipe\") else {
+        return vec![];
+    };
+
+    let mut sockets: Vec<IpcSocket> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let index = name.to_str()?.strip_prefix("discord-ipc-")?.parse().ok()?;
+            (index < IPC_SOCKET_COUNT).then(|| IpcSocket {
+                index,
+                owner: pipe_server_pid(&entry.path())
+                    .and_then(|pid| process_owner(pid, registry, table)),
+                path: entry.path(),
+            })
+        })
+        .collect();
+    sockets.sort_by_key(|socket| socket.index);
+    sockets
+}
+
+/// PID del proceso que atiende el named pipe. `None` si todas las
+/// instancias están ocupadas.
+#[cfg(windows)]
+fn pipe_server_pid(path: &Path) -> Option<u32> {
+    use std::os::windows::io::AsRawHandle;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetNamedPipeServerProcessId(pipe: *mut std::ffi::c_void, pid: *mut u32) -> i32;
+    }
+
+    let pipe = std::fs::OpenOptions::new().read(true).open(path).ok()?;
+    let mut pid = 0;
+    // SAFETY: el handle es válido mientras `pipe` exista y `pid` es un
+    // buffer válido para un u32
+    let result = unsafe { GetNamedPipeServerProcessId(pipe.as_raw_handle(), &mut pid) };
+    (result != 0 && pid > 0).then_some(pid)
+}
+
+/// Cómo está instalado Discord
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallKind {
+    /// Instalador de Windows con `Update.exe`
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    Squirrel,
+    /// Ejecutable suelto en Windows
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    Executable,
+    Tarball,
+    /// Paquete de la distribución (deb, rpm, AUR...)
+    Package,
+    Flatpak,
+    Snap,
+    /// Comando indicado por el usuario
+    Custom,
+}
+
+impl InstallKind {
+    pub fn lang_key(&self) -> &'static str {
+        match self {
+            InstallKind::Squirrel => "install_kind_windows",
+            InstallKind::Executable => "install_kind_executable",
+            InstallKind::Tarball => "install_kind_tarball",
+            InstallKind::Package => "install_kind_package",
+            InstallKind::Flatpak => "install_kind_flatpak",
+            InstallKind::Snap => "install_kind_snap",
+            InstallKind::Custom => "install_kind_custom",
+        }
+    }
+}
+
+/// Programa y argumentos para abrir una instalación
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchCommand {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+}
+
+impl LaunchCommand {
+    pub fn new(program: impl Into<PathBuf>, args: &[&str]) -> Self {
+        Self {
+            program: program.into(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    /// Interpreta una línea como la de `Exec` en los `.desktop`:
+    /// respeta comillas y quita códigos como `%U`
+    pub fn parse(command: &str) -> Option<Self> {
+        let mut words = split_command_line(command).into_iter();
+        Some(Self {
+            program: PathBuf::from(words.next()?),
+            args: words.collect(),
+        })
+    }
+}
+
+fn split_command_line(command: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
             }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
         }
     }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words.retain(|word| !(word.len() == 2 && word.starts_with('%')));
+    words
+}
+
+/// Cliente instalado y cómo abrirlo
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscordInstall {
+    pub client: DiscordClient,
+    pub kind: InstallKind,
+    pub launch: LaunchCommand,
+}
+
+/// Clientes que se pueden abrir: primero los que tienen un comando del
+/// usuario y después los conocidos que se encuentren instalados
+pub fn find_discord_installs(registry: &ClientRegistry) -> Vec<DiscordInstall> {
+    let mut found: Vec<DiscordInstall> = registry
+        .clients
+        .iter()
+        .filter_map(|client| {
+            Some(DiscordInstall {
+                launch: client.launch.clone()?,
+                client: client.clone(),
+                kind: InstallKind::Custom,
+            })
+        })
+        .collect();
+
+    #[cfg(target_os = "linux")]
+    found.extend(super::discord_linux::find_installs(registry));
+    #[cfg(not(target_os = "linux"))]
+    found.extend(find_windows_installs(registry));
     found
 }
 
-pub fn open_discord(version: DiscordVersion) -> std::io::Result<()> {
-    if let Some(local_appdata) = std::env::var_os("LOCALAPPDATA") {
-        let update_exe: PathBuf = Path::new(&local_appdata)
-            .join(version.folder_name())
-            .join("Update.exe");
-        if update_exe.exists() {
-            Command::new(update_exe).arg("--processStart").arg(version.exe_name()).spawn()?;
+#[cfg(not(target_os = "linux"))]
+fn find_windows_installs(registry: &ClientRegistry) -> Vec<DiscordInstall> {
+    let Some(local_appdata) = std::env::var_os("LOCALAPPDATA") else {
+        return vec![];
+    };
+    let local_appdata = Path::new(&local_appdata);
+
+    let mut found = vec![];
+    for client in &registry.clients {
+        let Some(builtin) = client.builtin else {
+            continue;
+        };
+
+        if let Some((folder, exe)) = builtin.squirrel {
+            let update_exe = local_appdata.join(folder).join("Update.exe");
+            if update_exe.exists() {
+                found.push(DiscordInstall {
+                    client: client.clone(),
+                    kind: InstallKind::Squirrel,
+                    launch: LaunchCommand::new(update_exe, &["--processStart", exe]),
+                });
+                continue;
+            }
+        }
+
+        if let Some(path) = builtin
+            .windows_executables
+            .iter()
+            .map(|exe| local_appdata.join(exe))
+            .find(|path| path.exists())
+        {
+            found.push(DiscordInstall {
+                client: client.clone(),
+                kind: InstallKind::Executable,
+                launch: LaunchCommand::new(path, &[]),
+            });
         }
     }
+    found
+}
+
+pub fn open_discord(install: &DiscordInstall) -> std::io::Result<()> {
+    log::info!(
+        "Launching {} with {} {}",
+        install.client.name,
+        install.launch.program.display(),
+        install.launch.args.join(" ")
+    );
+    Command::new(&install.launch.program)
+        .args(&install.launch.args)
+        .spawn()?;
     Ok(())
 }
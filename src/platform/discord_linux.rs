@@ -0,0 +1,279 @@
+//! Búsqueda de clientes de Discord en Linux: tarball, paquetes de la
+//! distribución, Flatpak y Snap, por rutas conocidas y por archivos
+//! `.desktop`.
+
+use super::clients::{ClientRegistry, DiscordClient};
+use super::discord::{DiscordInstall, InstallKind, LaunchCommand};
+use std::path::{Path, PathBuf};
+
+pub fn find_installs(registry: &ClientRegistry) -> Vec<DiscordInstall> {
+    let mut found = vec![];
+
+    for client in &registry.clients {
+        let Some(builtin) = client.builtin else {
+            continue;
+        };
+
+        for (kind, path) in builtin.linux_executables {
+            let path = expand_home(path);
+            if is_executable(&path) {
+                add_install(&mut found, client, *kind, LaunchCommand::new(path, &[]));
+            }
+        }
+
+        if let Some(app_id) = builtin.flatpak_id {
+            if flatpak_roots().iter().any(|root| root.join(app_id).is_dir()) {
+                add_install(
+                    &mut found,
+                    client,
+                    InstallKind::Flatpak,
+                    LaunchCommand::new("flatpak", &["run", app_id]),
+                );
+            }
+        }
+
+        if let Some(name) = builtin.snap {
+            if Path::new("/snap").join(name).join("current").exists() {
+                add_install(
+                    &mut found,
+                    client,
+                    InstallKind::Snap,
+                    LaunchCommand::new("snap", &["run", name]),
+                );
+            }
+        }
+    }
+
+    // Los `.desktop` cubren instalaciones en rutas que no conocemos
+    for directory in application_directories() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "desktop") {
+                if let Some(install) = parse_desktop_file(&path, registry) {
+                    add_install(&mut found, &install.client, install.kind, install.launch);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Agrega la instalación si no hay otra del mismo tipo ni otra que
+/// apunte al mismo ejecutable
+fn add_install(
+    found: &mut Vec<DiscordInstall>,
+    client: &DiscordClient,
+    kind: InstallKind,
+    launch: LaunchCommand,
+) {
+    let duplicate = found.iter().any(|install| {
+        install.client.name == client.name
+            && (install.kind == kind || same_file(&install.launch.program, &launch.program))
+    });
+    if !duplicate {
+        log::debug!("Found {} ({:?}) at {}", client.name, kind, launch.program.display());
+        found.push(DiscordInstall {
+            client: client.clone(),
+            kind,
+            launch,
+        });
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn data_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".local/share")))
+}
+
+fn flatpak_roots() -> Vec<PathBuf> {
+    let mut roots = vec![PathBuf::from("/var/lib/flatpak/app")];
+    if let Some(data) = data_home() {
+        roots.push(data.join("flatpak/app"));
+    }
+    roots
+}
+
+/// Carpetas `applications` según XDG, más las de Flatpak y Snap
+fn application_directories() -> Vec<PathBuf> {
+    let mut directories = vec![];
+    if let Some(data) = data_home() {
+        directories.push(data.join("applications"));
+        directories.push(data.join("flatpak/exports/share/applications"));
+    }
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    directories.extend(data_dirs.split(':').map(|dir| Path::new(dir).join("applications")));
+
+    directories.push(PathBuf::from("/var/lib/flatpak/exports/share/applications"));
+    directories.push(PathBuf::from("/var/lib/snapd/desktop/applications"));
+    directories.dedup();
+    directories
+}
+
+/// Cliente conocido según el nombre del archivo: `discord-canary.desktop`,
+/// `com.discordapp.Discord.desktop` o `discord_discord.desktop` (Snap)
+fn client_from_desktop_name<'a>(
+    path: &Path,
+    registry: &'a ClientRegistry,
+) -> Option<&'a DiscordClient> {
+    let stem = path.file_stem()?.to_str()?;
+    let process = stem.split('_').next().unwrap_or(stem);
+    registry.clients.iter().find(|client| {
+        client.builtin.is_some_and(|builtin| {
+            builtin.flatpak_id == Some(stem) || client.matches_process(process)
+        })
+    })
+}
+
+fn parse_desktop_file(path: &Path, registry: &ClientRegistry) -> Option<DiscordInstall> {
+    let client = client_from_desktop_name(path, registry)?;
+    let contents = std::fs::read_to_string(path).ok()?;
+    parse_desktop_entry(client, &contents)
+}
+
+/// Interpreta el grupo `[Desktop Entry]`. Las entradas con `Hidden=true`
+/// cuentan como borradas; `NoDisplay` solo las oculta del menú.
+fn parse_desktop_entry(client: &DiscordClient, contents: &str) -> Option<DiscordInstall> {
+    let mut in_entry = false;
+    let mut exec = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+        } else if in_entry {
+            if let Some(value) = line.strip_prefix("Exec=") {
+                exec = Some(value.to_string());
+            } else if line == "Hidden=true" {
+                return None;
+            }
+        }
+    }
+
+    // Snap usa `env VAR=valor /snap/bin/discord`
+    let mut launch = LaunchCommand::parse(&exec?)?;
+    while launch.program == Path::new("env") || launch.program.to_string_lossy().contains('=') {
+        if launch.args.is_empty() {
+            return None;
+        }
+        launch.program = PathBuf::from(launch.args.remove(0));
+    }
+
+    let program = &launch.program;
+    let kind = if program.ends_with("flatpak") {
+        InstallKind::Flatpak
+    } else if program.starts_with("/snap") || program.ends_with("snap") {
+        InstallKind::Snap
+    } else if program.is_relative() || program.starts_with("/usr") {
+        InstallKind::Package
+    } else {
+        InstallKind::Tarball
+    };
+
+    Some(DiscordInstall {
+        client: client.clone(),
+        kind,
+        launch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discord() -> DiscordClient {
+        ClientRegistry::default()
+            .by_process_name("Discord")
+            .unwrap()
+            .clone()
+    }
+
+    fn args(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn launch_command_handles_quotes_and_field_codes() {
+        let launch =
+            LaunchCommand::parse(r#""/opt/Discord App/Discord" --name "say \"hi\"" %U"#).unwrap();
+        assert_eq!(launch.program, Path::new("/opt/Discord App/Discord"));
+        assert_eq!(launch.args, args(&["--name", r#"say "hi""#]));
+
+        let launch = LaunchCommand::parse("discord %f --start-minimized").unwrap();
+        assert_eq!(launch.program, Path::new("discord"));
+        assert_eq!(launch.args, args(&["--start-minimized"]));
+
+        assert_eq!(LaunchCommand::parse("  "), None);
+    }
+
+    #[test]
+    fn desktop_entry_reads_only_its_group() {
+        let contents = "\
+[Desktop Entry]
+Name=Discord
+Exec=/usr/share/discord/Discord %U
+
+[Desktop Action new-window]
+Exec=/usr/share/discord/Discord --new-window
+";
+        let install = parse_desktop_entry(&discord(), contents).unwrap();
+        assert_eq!(install.kind, InstallKind::Package);
+        assert_eq!(install.launch.program, Path::new("/usr/share/discord/Discord"));
+        assert!(install.launch.args.is_empty());
+
+        let contents = "[Desktop Action new-window]\nExec=/opt/discord/Discord\n";
+        assert_eq!(parse_desktop_entry(&discord(), contents), None);
+    }
+
+    #[test]
+    fn desktop_entry_skips_hidden_entries() {
+        let hidden = "[Desktop Entry]\nExec=/opt/discord/Discord\nHidden=true\n";
+        assert_eq!(parse_desktop_entry(&discord(), hidden), None);
+
+        let no_display = "[Desktop Entry]\nExec=/opt/discord/Discord\nNoDisplay=true\n";
+        let install = parse_desktop_entry(&discord(), no_display).unwrap();
+        assert_eq!(install.kind, InstallKind::Tarball);
+    }
+
+    #[test]
+    fn desktop_entry_unwraps_snap_env() {
+        let contents = "[Desktop Entry]
+Exec=env BAMF_DESKTOP_FILE_HINT=/var/lib/snapd/desktop/applications/discord_discord.desktop \
+/snap/bin/discord %U
+";
+        let install = parse_desktop_entry(&discord(), contents).unwrap();
+        assert_eq!(install.kind, InstallKind::Snap);
+        assert_eq!(install.launch.program, Path::new("/snap/bin/discord"));
+    }
+}
@@ -1,2 +1,6 @@
 pub mod update;
+pub mod clients;
+pub mod console;
 pub mod discord;
+#[cfg(target_os = "linux")]
+mod discord_linux;
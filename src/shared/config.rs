@@ -1,11 +1,78 @@
+use crate::core::presets::write_atomic;
 use crate::shared::types::Config;
 use std::fs;
 
-pub fn save_config(config: &Config) {
-    let _ = fs::write("config.json", serde_json::to_string_pretty(config).unwrap_or_default());
+const CONFIG_FILE: &str = "config.json";
+/// Copia de un config.json que no se pudo interpretar
+const CONFIG_BACKUP_FILE: &str = "config.json.bak";
+
+/// Guarda config.json. Devuelve `false` si no se pudo escribir.
+pub fn save_config(config: &Config) -> bool {
+    let json = serde_json::to_string_pretty(config).unwrap_or_default();
+    if let Err(e) = write_atomic(CONFIG_FILE, json.as_bytes()) {
+        log::error!("Failed to save {}: {}", CONFIG_FILE, e);
+        return false;
+    }
+    true
 }
 
-pub fn load_config() -> Config {
-    let data = fs::read_to_string("config.json").unwrap_or_default();
-    serde_json::from_str(&data).unwrap_or_else(|_| Config::new())
+/// Lee config.json; si no existe devuelve la configuración por defecto.
+/// Si no se puede interpretar guarda una copia en config.json.bak y
+/// devuelve el error: quien lo reciba no debe guardar encima.
+pub fn load_config() -> Result<Config, String> {
+    let data = match fs::read_to_string(CONFIG_FILE) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::new()),
+        Err(e) => return Err(format!("{}: {}", CONFIG_FILE, e)),
+    };
+
+    serde_json::from_str(&data).map_err(|e| {
+        let error = format!("{}: {}", CONFIG_FILE, e);
+        log::error!("Failed to parse {}", error);
+        match fs::copy(CONFIG_FILE, CONFIG_BACKUP_FILE) {
+            Ok(_) => log::warn!("Saved a copy of {} to {}", CONFIG_FILE, CONFIG_BACKUP_FILE),
+            Err(e) => log::warn!("Failed to back up {}: {}", CONFIG_FILE, e),
+        }
+        error
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::in_temp_dir;
+
+    #[test]
+    fn missing_config_uses_defaults() {
+        in_temp_dir(|_| {
+            assert!(load_config().is_ok());
+        });
+    }
+
+    #[test]
+    fn invalid_config_is_reported_and_backed_up() {
+        in_temp_dir(|_| {
+            let broken = r#"{"language": "English", "custom_clients": [}"#;
+            fs::write(CONFIG_FILE, broken).unwrap();
+
+            let Err(error) = load_config() else {
+                panic!("invalid config.json was accepted");
+            };
+
+            assert!(error.starts_with(CONFIG_FILE));
+            assert_eq!(fs::read_to_string(CONFIG_FILE).unwrap(), broken);
+            assert_eq!(fs::read_to_string(CONFIG_BACKUP_FILE).unwrap(), broken);
+        });
+    }
+
+    #[test]
+    fn saved_config_loads_back() {
+        in_temp_dir(|_| {
+            let mut config = Config::new();
+            config.language = "English".to_string();
+            save_config(&config);
+
+            assert_eq!(load_config().unwrap().language, "English");
+        });
+    }
 }
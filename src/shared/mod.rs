@@ -1,4 +1,7 @@
 pub mod config;
 pub mod lang;
+pub mod process_table;
+#[cfg(all(test, target_os = "linux"))]
+mod process_table_bench;
 pub mod types;
 pub mod richpresence;
\ No newline at end of file
@@ -0,0 +1,76 @@
+//! Tabla de procesos compartida entre la detección de Discord y el
+//! monitor de sesiones. Solo carga procesos (no discos, redes ni
+//! sensores) y puede refrescar únicamente los PID que interesan.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+#[derive(Clone)]
+pub struct ProcessTable {
+    system: Arc<Mutex<System>>,
+}
+
+impl ProcessTable {
+    pub fn new() -> Self {
+        Self {
+            system: Arc::new(Mutex::new(System::new())),
+        }
+    }
+
+    /// Refresca solo estos PID y quita los que ya terminaron
+    pub fn refresh_pids(&self, pids: &[u32]) {
+        if pids.is_empty() {
+            return;
+        }
+        let pids: Vec<Pid> = pids.iter().map(|pid| Pid::from_u32(*pid)).collect();
+        self.refresh(ProcessesToUpdate::Some(&pids));
+    }
+
+    /// Vuelve a listar todos los procesos, sin CPU, memoria ni rutas
+    pub fn refresh_all(&self) {
+        self.refresh(ProcessesToUpdate::All);
+    }
+
+    fn refresh(&self, processes: ProcessesToUpdate) {
+        if let Some(mut system) = self.lock() {
+            system.refresh_processes_specifics(processes, true, ProcessRefreshKind::nothing());
+        }
+    }
+
+    /// Nombre del proceso según el último refresco
+    pub fn name(&self, pid: u32) -> Option<String> {
+        let system = self.lock()?;
+        let process = system.process(Pid::from_u32(pid))?;
+        Some(process.name().to_string_lossy().into_owned())
+    }
+
+    /// Nombres de todos los procesos según el último refresco
+    pub fn names(&self) -> Vec<String> {
+        self.lock()
+            .map(|system| {
+                system
+                    .processes()
+                    .values()
+                    .map(|process| process.name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Acceso directo al `System`, por ejemplo para terminar un proceso
+    pub fn lock(&self) -> Option<MutexGuard<'_, System>> {
+        match self.system.lock() {
+            Ok(system) => Some(system),
+            Err(_) => {
+                log::error!("Failed to acquire process table lock");
+                None
+            }
+        }
+    }
+}
+
+impl Default for ProcessTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,94 @@
+//! Compara el costo de la detección de Discord y del monitor de
+//! sesiones antes y después de compartir la tabla de procesos.
+//!
+//! `cargo test --release process_table -- --ignored --nocapture`
+
+use super::process_table::ProcessTable;
+use crate::platform::clients::ClientRegistry;
+use crate::platform::discord::detect_discord;
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessesToUpdate, System};
+
+/// Un minuto de la app: el monitor revisa cada 2 s y la detección cada 5 s
+const SIMULATED_SECONDS: u32 = 60;
+const MONITOR_INTERVAL: u32 = 2;
+const DETECTION_INTERVAL: u32 = 5;
+const SESSIONS: usize = 10;
+
+/// Tiempo de CPU del proceso, sumando todos sus hilos
+fn cpu_time() -> Duration {
+    // SAFETY: `usage` es un buffer válido para `getrusage`
+    let usage = unsafe {
+        let mut usage = std::mem::zeroed::<libc::rusage>();
+        libc::getrusage(libc::RUSAGE_SELF, &mut usage);
+        usage
+    };
+    let to_duration =
+        |t: libc::timeval| Duration::new(t.tv_sec as u64, t.tv_usec as u32 * 1000);
+    to_duration(usage.ru_utime) + to_duration(usage.ru_stime)
+}
+
+fn measure(name: &str, f: impl FnOnce()) -> Duration {
+    let wall = Instant::now();
+    let cpu = cpu_time();
+    f();
+    let cpu = cpu_time() - cpu;
+    println!(
+        "{:<8} cpu {:>8.1} ms  wall {:>8.1} ms",
+        name,
+        cpu.as_secs_f64() * 1000.0,
+        wall.elapsed().as_secs_f64() * 1000.0
+    );
+    cpu
+}
+
+#[test]
+#[ignore = "benchmark; run with --ignored --nocapture"]
+fn process_table_reduces_cpu() {
+    // Procesos sin handle propio: el peor caso para el monitor nuevo
+    let mut sessions: Vec<Child> = (0..SESSIONS)
+        .map(|_| Command::new("sleep").arg("60").spawn().expect("spawn sleep"))
+        .collect();
+    let pids: Vec<u32> = sessions.iter().map(Child::id).collect();
+    let registry = ClientRegistry::default();
+
+    let before = measure("before", || {
+        let mut monitor = System::new_all();
+        for second in 0..SIMULATED_SECONDS {
+            if second % MONITOR_INTERVAL == 0 {
+                monitor.refresh_processes(ProcessesToUpdate::All, true);
+            }
+            if second % DETECTION_INTERVAL == 0 {
+                let system = System::new_all();
+                let running = ["Discord", "DiscordPTB", "DiscordCanary"]
+                    .iter()
+                    .any(|name| system.processes_by_name(name.as_ref()).next().is_some());
+                std::hint::black_box(running);
+            }
+        }
+    });
+
+    let after = measure("after", || {
+        let table = ProcessTable::new();
+        for second in 0..SIMULATED_SECONDS {
+            if second % MONITOR_INTERVAL == 0 {
+                table.refresh_pids(&pids);
+            }
+            if second % DETECTION_INTERVAL == 0 {
+                std::hint::black_box(detect_discord(&registry, &table));
+            }
+        }
+    });
+
+    for session in &mut sessions {
+        let _ = session.kill();
+        let _ = session.wait();
+    }
+
+    println!(
+        "{:.1}x less CPU",
+        before.as_secs_f64() / after.as_secs_f64().max(f64::EPSILON)
+    );
+    assert!(after < before);
+}
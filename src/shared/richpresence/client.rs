@@ -2,25 +2,26 @@ use discord_rich_presence::{
     activity::{Activity, Assets, Button, Timestamps},
     DiscordIpc, DiscordIpcClient,
 };
+use super::template::{is_valid_text, ActivityContent};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const CLIENT_ID: &str = "1391260707542143046";
-
 pub struct RichPresenceManager {
+    client_id: String,
     client: Option<DiscordIpcClient>,
     is_connected: bool,
     start_time: i64,
-    last_activity: Option<String>, // Track last activity to avoid redundant updates
+    last_activity: Option<ActivityContent>, // Track last activity to avoid redundant updates
 }
 
 impl RichPresenceManager {
-    pub fn new() -> Self {
+    pub fn new(client_id: &str) -> Self {
         let start_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs() as i64;
 
         Self {
+            client_id: client_id.to_string(),
             client: None,
             is_connected: false,
             start_time,
@@ -34,7 +35,7 @@ impl RichPresenceManager {
             return Ok(());
         }
 
-        let mut client = DiscordIpcClient::new(CLIENT_ID);
+        let mut client = DiscordIpcClient::new(&self.client_id);
 
         match client.connect() {
             Ok(_) => {
@@ -44,7 +45,7 @@ impl RichPresenceManager {
                 Ok(())
             }
             Err(e) => {
-                log::error!("Failed to connect Rich Presence: {}", e);
+                log::warn!("Failed to connect Rich Presence: {}", e);
                 self.is_connected = false;
                 Err(Box::new(e))
             }
@@ -68,49 +69,62 @@ impl RichPresenceManager {
 
     pub fn set_activity(
         &mut self,
-        game_name: Option<String>,
+        content: &ActivityContent,
     ) -> Result<(), Box<dyn std::error::Error>> {
         if !self.is_connected {
             return Err("Rich Presence not connected".into());
         }
 
         // Avoid redundant updates
-        if self.last_activity == game_name {
+        if self.last_activity.as_ref() == Some(content) {
             log::debug!("Skipping redundant activity update");
             return Ok(());
         }
 
         let client = self.client.as_mut().ok_or("Client not initialized")?;
 
-        let mut activity = Activity::new()
-            .timestamps(Timestamps::new().start(self.start_time))
-            .assets(
-                Assets::new()
-                    .large_image("dsqprocess_logo")
-                    .large_text("DSQProcess - Discord Quest Process"),
-            );
+        let mut assets = Assets::new().large_image(&content.large_image);
+        if is_valid_text(&content.large_text) {
+            assets = assets.large_text(&content.large_text);
+        }
+        if let Some(small_image) = &content.small_image {
+            assets = assets.small_image(small_image);
+        }
+        if let Some(small_text) = content.small_text.as_ref().filter(|t| is_valid_text(t)) {
+            assets = assets.small_text(small_text);
+        }
 
-        // Add button
-        let button = Button::new(
-            "Ver repositorio",
-            "https://github.com/Nicolhetti/DSQProcess",
-        );
-        activity = activity.buttons(vec![button]);
-
-        // Set state based on game
-        if let Some(ref game) = game_name {
-            let state_text = format!("Jugando: {}", game);
-            let leaked_state: &'static str = Box::leak(state_text.into_boxed_str());
-            activity = activity.state(leaked_state).details("Simulando juego");
-            log::info!("Setting Rich Presence to: {}", game);
-        } else {
-            activity = activity.state("Esperando...").details("Sin juego activo");
-            log::info!("Setting Rich Presence to idle");
+        // Sin sesión se muestra el tiempo desde que se abrió la app
+        let mut timestamps =
+            Timestamps::new().start(content.start_timestamp.unwrap_or(self.start_time));
+        if let Some(end) = content.end_timestamp {
+            timestamps = timestamps.end(end);
+        }
+
+        let mut activity = Activity::new().timestamps(timestamps).assets(assets);
+
+        if is_valid_text(&content.details) {
+            activity = activity.details(&content.details);
+        }
+        if is_valid_text(&content.state) {
+            activity = activity.state(&content.state);
+        }
+
+        if !content.buttons.is_empty() {
+            activity = activity.buttons(
+                content
+                    .buttons
+                    .iter()
+                    .map(|button| Button::new(&button.label, &button.url))
+                    .collect(),
+            );
         }
 
+        log::info!("Setting Rich Presence to: {} / {}", content.details, content.state);
+
         match client.set_activity(activity) {
             Ok(_) => {
-                self.last_activity = game_name;
+                self.last_activity = Some(content.clone());
                 Ok(())
             }
             Err(e) => {
@@ -144,14 +158,13 @@ impl RichPresenceManager {
         }
     }
 
-    /// Reconnect if disconnected (useful for recovery)
-    #[allow(dead_code)]
-    pub fn ensure_connected(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if !self.is_connected {
-            log::info!("Attempting to reconnect Rich Presence");
-            self.connect()?;
-        }
-        Ok(())
+    /// Vuelve a enviar la actividad aunque no haya cambiado
+    pub fn resend_activity(
+        &mut self,
+        content: &ActivityContent,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.last_activity = None;
+        self.set_activity(content)
     }
 }
 
@@ -161,9 +174,3 @@ impl Drop for RichPresenceManager {
         self.disconnect();
     }
 }
-
-impl Default for RichPresenceManager {
-    fn default() -> Self {
-        Self::new()
-    }
-}
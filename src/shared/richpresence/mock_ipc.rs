@@ -0,0 +1,214 @@
+//! Servidor IPC falso de Discord para las pruebas de Rich Presence.
+//!
+//! Escucha en `discord-ipc-0` dentro de una carpeta temporal y apunta
+//! `XDG_RUNTIME_DIR` a ella, que es donde el cliente busca el socket.
+//! Solo existe en Unix; en Windows Discord usa named pipes.
+
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+const OP_PING: u32 = 3;
+const OP_PONG: u32 = 4;
+
+/// Las pruebas cambian una variable de entorno del proceso, así que
+/// no pueden correr a la vez
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct MockState {
+    /// `client_id` de cada handshake recibido
+    handshakes: Vec<String>,
+    /// Actividad de cada SET_ACTIVITY; `null` cuando se borró
+    activities: Vec<Value>,
+    /// Conexiones abiertas, para poder cortarlas
+    streams: Vec<UnixStream>,
+    /// Conexiones aceptadas desde el inicio
+    connections: usize,
+}
+
+pub struct MockDiscord {
+    dir: PathBuf,
+    state: Arc<Mutex<MockState>>,
+    running: Arc<AtomicBool>,
+    listener: Option<JoinHandle<()>>,
+    _env: MutexGuard<'static, ()>,
+}
+
+impl MockDiscord {
+    pub fn start() -> Self {
+        let env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = std::env::temp_dir().join(format!(
+            "dsq-mock-ipc-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir).expect("create mock IPC directory");
+        std::env::set_var("XDG_RUNTIME_DIR", &dir);
+        std::env::remove_var("SNAP");
+
+        let mut mock = Self {
+            dir,
+            state: Arc::new(Mutex::new(MockState::default())),
+            running: Arc::new(AtomicBool::new(false)),
+            listener: None,
+            _env: env,
+        };
+        mock.listen();
+        mock
+    }
+
+    fn socket_path(&self) -> PathBuf {
+        self.dir.join("discord-ipc-0")
+    }
+
+    fn listen(&mut self) {
+        let _ = std::fs::remove_file(self.socket_path());
+        let listener = UnixListener::bind(self.socket_path()).expect("bind mock IPC socket");
+        listener
+            .set_nonblocking(true)
+            .expect("set mock IPC socket non-blocking");
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = Arc::clone(&self.running);
+        let state = Arc::clone(&self.state);
+
+        self.listener = Some(std::thread::spawn(move || {
+            while running.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let _ = stream.set_nonblocking(false);
+                        let mut locked = state.lock().unwrap();
+                        locked.connections += 1;
+                        if let Ok(clone) = stream.try_clone() {
+                            locked.streams.push(clone);
+                        }
+                        drop(locked);
+                        let state = Arc::clone(&state);
+                        std::thread::spawn(move || serve(stream, state));
+                    }
+                    Err(_) => std::thread::sleep(Duration::from_millis(10)),
+                }
+            }
+        }));
+    }
+
+    /// Corta las conexiones abiertas, como si Discord las cerrara
+    pub fn disconnect_clients(&self) {
+        for stream in self.state.lock().unwrap().streams.drain(..) {
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+        }
+    }
+
+    /// Simula que Discord se cerró: corta las conexiones y quita el socket
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+        self.disconnect_clients();
+        let _ = std::fs::remove_file(self.socket_path());
+    }
+
+    /// Simula que Discord volvió a abrirse
+    pub fn restart(&mut self) {
+        self.stop();
+        self.listen();
+    }
+
+    pub fn connections(&self) -> usize {
+        self.state.lock().unwrap().connections
+    }
+
+    pub fn handshakes(&self) -> Vec<String> {
+        self.state.lock().unwrap().handshakes.clone()
+    }
+
+    pub fn activities(&self) -> Vec<Value> {
+        self.state.lock().unwrap().activities.clone()
+    }
+
+    /// Espera hasta recibir `count` actividades o hasta que pase `timeout`
+    pub fn wait_for_activities(&self, count: usize, timeout: Duration) -> Vec<Value> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let activities = self.activities();
+            if activities.len() >= count || Instant::now() >= deadline {
+                return activities;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+impl Drop for MockDiscord {
+    fn drop(&mut self) {
+        self.stop();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Atiende una conexión hasta que el cliente la cierra
+fn serve(mut stream: UnixStream, state: Arc<Mutex<MockState>>) {
+    while let Some((opcode, payload)) = read_frame(&mut stream) {
+        let reply = match opcode {
+            OP_HANDSHAKE => {
+                let client_id = payload["client_id"].as_str().unwrap_or_default().to_string();
+                state.lock().unwrap().handshakes.push(client_id);
+                json!({
+                    "cmd": "DISPATCH",
+                    "evt": "READY",
+                    "data": { "v": 1, "user": { "id": "0", "username": "mock" } }
+                })
+            }
+            OP_FRAME if payload["cmd"] == "SET_ACTIVITY" => {
+                let activity = payload["args"]["activity"].clone();
+                state.lock().unwrap().activities.push(activity.clone());
+                json!({
+                    "cmd": "SET_ACTIVITY",
+                    "data": activity,
+                    "evt": null,
+                    "nonce": payload["nonce"]
+                })
+            }
+            OP_PING => payload,
+            OP_CLOSE => return,
+            _ => continue,
+        };
+
+        let reply_opcode = if opcode == OP_PING { OP_PONG } else { OP_FRAME };
+        if write_frame(&mut stream, reply_opcode, &reply).is_err() {
+            return;
+        }
+    }
+}
+
+/// Cada mensaje es `opcode` y largo en u32 little endian seguidos del JSON
+fn read_frame(stream: &mut UnixStream) -> Option<(u32, Value)> {
+    let mut header = [0u8; 8];
+    stream.read_exact(&mut header).ok()?;
+    let opcode = u32::from_le_bytes(header[..4].try_into().ok()?);
+    let length = u32::from_le_bytes(header[4..].try_into().ok()?);
+
+    let mut body = vec![0u8; length as usize];
+    stream.read_exact(&mut body).ok()?;
+    Some((opcode, serde_json::from_slice(&body).ok()?))
+}
+
+fn write_frame(stream: &mut UnixStream, opcode: u32, payload: &Value) -> std::io::Result<()> {
+    let body = payload.to_string();
+    let mut frame = Vec::with_capacity(8 + body.len());
+    frame.extend_from_slice(&opcode.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(body.as_bytes());
+    stream.write_all(&frame)
+}
@@ -1,3 +1,10 @@
 pub mod client;
+pub mod template;
+pub mod worker;
 
-pub use client::RichPresenceManager;
\ No newline at end of file
+#[cfg(all(test, unix))]
+mod mock_ipc;
+#[cfg(all(test, unix))]
+mod tests;
+
+pub use worker::{PresenceStatus, RichPresenceWorker};
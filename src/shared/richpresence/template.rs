@@ -0,0 +1,245 @@
+use crate::core::history::format_duration;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Aplicación de Discord por defecto
+pub const DEFAULT_CLIENT_ID: &str = "1391260707542143046";
+/// Imagen grande que se usa si la plantilla no indica otra
+pub const DEFAULT_LARGE_IMAGE: &str = "dsqprocess_logo";
+pub const REPOSITORY_URL: &str = "https://github.com/Nicolhetti/DSQProcess";
+
+/// Variables que se pueden usar en los textos de la actividad
+pub const ACTIVITY_PLACEHOLDERS: &[&str] = &["{game}", "{elapsed}", "{remaining}", "{sessions}"];
+
+/// Límites de Discord para los textos de la actividad
+const MAX_TEXT_CHARS: usize = 128;
+const MAX_BUTTON_LABEL_CHARS: usize = 32;
+const MAX_BUTTONS: usize = 2;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ActivityButton {
+    pub label: String,
+    pub url: String,
+}
+
+/// Plantilla de la actividad de Rich Presence. Un texto vacío usa el
+/// texto por defecto del idioma seleccionado.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ActivityTemplate {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub details: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub state: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub idle_details: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub idle_state: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub large_image: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub large_text: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub small_image: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub small_text: String,
+    /// Sin valor se muestra el botón del repositorio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buttons: Option<Vec<ActivityButton>>,
+    #[serde(default)]
+    pub multi_session: MultiSessionDisplay,
+    /// Imagen grande por preset: nombre del preset -> clave del recurso
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub preset_images: BTreeMap<String, String>,
+}
+
+/// Qué mostrar cuando hay varias sesiones simuladas a la vez
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiSessionDisplay {
+    /// La sesión iniciada más recientemente
+    #[default]
+    MostRecent,
+    /// Un resumen como "2 juegos", desde el inicio de la primera sesión
+    Aggregate,
+}
+
+impl MultiSessionDisplay {
+    pub const ALL: [MultiSessionDisplay; 2] =
+        [MultiSessionDisplay::MostRecent, MultiSessionDisplay::Aggregate];
+
+    pub fn lang_key(&self) -> &'static str {
+        match self {
+            MultiSessionDisplay::MostRecent => "rp_multi_most_recent",
+            MultiSessionDisplay::Aggregate => "rp_multi_aggregate",
+        }
+    }
+}
+
+/// Valores de las variables de la plantilla
+#[derive(Clone, Debug, Default)]
+pub struct ActivityVars {
+    pub game: Option<String>,
+    /// Preset de la sesión mostrada, para buscar su imagen
+    pub preset: Option<String>,
+    pub elapsed_seconds: u64,
+    pub remaining_seconds: Option<u64>,
+    pub sessions: usize,
+    /// Inicio y cierre previsto de la sesión mostrada, en segundos Unix
+    pub start_timestamp: Option<i64>,
+    pub end_timestamp: Option<i64>,
+}
+
+/// Actividad ya resuelta que se envía a Discord
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActivityContent {
+    pub details: String,
+    pub state: String,
+    pub large_image: String,
+    pub large_text: String,
+    pub small_image: Option<String>,
+    pub small_text: Option<String>,
+    pub buttons: Vec<ActivityButton>,
+    pub start_timestamp: Option<i64>,
+    pub end_timestamp: Option<i64>,
+}
+
+impl ActivityTemplate {
+    /// Indica si la plantilla usa variables que cambian con el tiempo
+    pub fn is_time_dependent(&self, translate: impl Fn(&str) -> String) -> bool {
+        [
+            self.text_or(&self.details, "rp_details_playing", &translate),
+            self.text_or(&self.state, "rp_state_playing", &translate),
+        ]
+        .iter()
+        .any(|text| text.contains("{elapsed}") || text.contains("{remaining}"))
+    }
+
+    /// Botones configurados, o el del repositorio si no hay
+    pub fn buttons_or_default(&self, translate: impl Fn(&str) -> String) -> Vec<ActivityButton> {
+        self.buttons.clone().unwrap_or_else(|| {
+            vec![ActivityButton {
+                label: translate("rp_button_repository"),
+                url: REPOSITORY_URL.to_string(),
+            }]
+        })
+    }
+
+    fn text_or(&self, text: &str, key: &str, translate: impl Fn(&str) -> String) -> String {
+        if text.trim().is_empty() {
+            translate(key)
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn render(&self, vars: &ActivityVars, translate: impl Fn(&str) -> String) -> ActivityContent {
+        let (details, state) = if vars.game.is_some() {
+            (
+                self.text_or(&self.details, "rp_details_playing", &translate),
+                self.text_or(&self.state, "rp_state_playing", &translate),
+            )
+        } else {
+            (
+                self.text_or(&self.idle_details, "rp_details_idle", &translate),
+                self.text_or(&self.idle_state, "rp_state_idle", &translate),
+            )
+        };
+
+        // Las claves inválidas se ignoran para no romper la actividad
+        let preset_image = vars.preset.as_ref().and_then(|preset| {
+            self.preset_images
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(preset))
+                .map(|(_, key)| key.trim())
+        });
+        let large_image = preset_image
+            .into_iter()
+            .chain([self.large_image.trim()])
+            .find(|key| is_valid_asset_key(key))
+            .unwrap_or(DEFAULT_LARGE_IMAGE)
+            .to_string();
+        let small_image =
+            Some(self.small_image.trim().to_string()).filter(|key| is_valid_asset_key(key));
+        let small_text = Some(vars.fill(&self.small_text))
+            .filter(|text| small_image.is_some() && !text.trim().is_empty())
+            .map(|text| truncate(&text, MAX_TEXT_CHARS));
+
+        let buttons = self
+            .buttons_or_default(&translate)
+            .into_iter()
+            .filter(|button| !button.label.trim().is_empty() && button.url.starts_with("http"))
+            .take(MAX_BUTTONS)
+            .map(|button| ActivityButton {
+                label: truncate(&vars.fill(&button.label), MAX_BUTTON_LABEL_CHARS),
+                url: button.url,
+            })
+            .collect();
+
+        ActivityContent {
+            details: truncate(&vars.fill(&details), MAX_TEXT_CHARS),
+            state: truncate(&vars.fill(&state), MAX_TEXT_CHARS),
+            large_image,
+            large_text: truncate(
+                &vars.fill(&self.text_or(&self.large_text, "rp_large_text", &translate)),
+                MAX_TEXT_CHARS,
+            ),
+            small_image,
+            small_text,
+            buttons,
+            start_timestamp: vars.start_timestamp,
+            end_timestamp: vars.end_timestamp,
+        }
+    }
+}
+
+impl ActivityVars {
+    /// Reemplaza las variables en un texto
+    fn fill(&self, template: &str) -> String {
+        template
+            .replace("{game}", self.game.as_deref().unwrap_or_default())
+            .replace("{elapsed}", &format_duration(self.elapsed_seconds))
+            .replace(
+                "{remaining}",
+                &self.remaining_seconds.map(format_duration).unwrap_or_default(),
+            )
+            .replace("{sessions}", &self.sessions.to_string())
+    }
+}
+
+/// Recorta el texto al largo máximo que acepta Discord
+fn truncate(text: &str, max_chars: usize) -> String {
+    text.trim().chars().take(max_chars).collect()
+}
+
+/// Discord rechaza los textos de menos de 2 caracteres o de más de 128
+pub fn is_valid_text(text: &str) -> bool {
+    (2..=MAX_TEXT_CHARS).contains(&text.chars().count())
+}
+
+/// Los ID de aplicación de Discord son números de 17 a 20 dígitos
+pub fn is_valid_client_id(id: &str) -> bool {
+    (17..=20).contains(&id.len()) && id.chars().all(|c| c.is_ascii_digit())
+}
+
+/// ID configurado si es válido, o el de DSQProcess
+pub fn effective_client_id(configured: &str) -> &str {
+    let configured = configured.trim();
+    if is_valid_client_id(configured) {
+        configured
+    } else {
+        DEFAULT_CLIENT_ID
+    }
+}
+
+/// Clave de un recurso subido a la aplicación (minúsculas, números, `_` y `-`)
+/// o URL de una imagen externa
+pub fn is_valid_asset_key(key: &str) -> bool {
+    if key.starts_with("https://") {
+        return key.len() <= 256;
+    }
+    !key.is_empty()
+        && key.len() <= 32
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
@@ -0,0 +1,251 @@
+//! Pruebas de Rich Presence contra el servidor IPC falso
+
+use super::client::RichPresenceManager;
+use super::mock_ipc::MockDiscord;
+use super::template::{ActivityButton, ActivityContent};
+use super::{PresenceStatus, RichPresenceWorker};
+use crate::platform::clients::ClientRegistry;
+use crate::platform::discord::detect_discord;
+use crate::shared::process_table::ProcessTable;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+const CLIENT_ID: &str = "123456789012345678";
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn content(details: &str) -> ActivityContent {
+    ActivityContent {
+        details: details.to_string(),
+        state: "In a quest".to_string(),
+        large_image: "dsqprocess_logo".to_string(),
+        large_text: "DSQProcess".to_string(),
+        small_image: None,
+        small_text: None,
+        buttons: vec![ActivityButton {
+            label: "Repository".to_string(),
+            url: "https://example.com".to_string(),
+        }],
+        start_timestamp: Some(1_700_000_000),
+        end_timestamp: Some(1_700_000_900),
+    }
+}
+
+fn wait_for_status(worker: &RichPresenceWorker, status: PresenceStatus) -> bool {
+    let deadline = Instant::now() + TIMEOUT;
+    while Instant::now() < deadline {
+        if worker.status() == status {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+fn connect_sends_handshake() {
+    let mock = MockDiscord::start();
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+
+    manager.connect().expect("connect to mock Discord");
+
+    assert!(manager.is_connected());
+    assert_eq!(mock.handshakes(), vec![CLIENT_ID.to_string()]);
+}
+
+#[test]
+fn connect_fails_without_discord() {
+    let mut mock = MockDiscord::start();
+    mock.stop();
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+
+    assert!(manager.connect().is_err());
+    assert!(!manager.is_connected());
+}
+
+#[test]
+fn set_activity_sends_payload() {
+    let mock = MockDiscord::start();
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+    manager.connect().unwrap();
+
+    manager.set_activity(&content("Playing Quest")).unwrap();
+
+    let activities = mock.wait_for_activities(1, TIMEOUT);
+    assert_eq!(activities.len(), 1);
+    let activity = &activities[0];
+    assert_eq!(activity["details"], "Playing Quest");
+    assert_eq!(activity["state"], "In a quest");
+    assert_eq!(activity["timestamps"]["start"], 1_700_000_000);
+    assert_eq!(activity["timestamps"]["end"], 1_700_000_900);
+    assert_eq!(activity["assets"]["large_image"], "dsqprocess_logo");
+    assert_eq!(activity["buttons"][0]["label"], "Repository");
+    assert_eq!(activity["buttons"][0]["url"], "https://example.com");
+}
+
+#[test]
+fn short_texts_are_omitted() {
+    let mock = MockDiscord::start();
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+    manager.connect().unwrap();
+
+    let mut activity = content("x");
+    activity.large_text = "D".to_string();
+    activity.small_image = Some("status".to_string());
+    activity.small_text = Some("1".to_string());
+    activity.buttons.clear();
+    manager.set_activity(&activity).unwrap();
+
+    let activities = mock.wait_for_activities(1, TIMEOUT);
+    assert!(activities[0].get("details").is_none());
+    assert_eq!(activities[0]["assets"]["small_image"], "status");
+    assert!(activities[0]["assets"].get("large_text").is_none());
+    assert!(activities[0]["assets"].get("small_text").is_none());
+    assert!(activities[0].get("buttons").is_none());
+}
+
+#[test]
+fn clear_activity_sends_null() {
+    let mock = MockDiscord::start();
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+    manager.connect().unwrap();
+
+    manager.set_activity(&content("Playing Quest")).unwrap();
+    manager.clear_activity().unwrap();
+
+    let activities = mock.wait_for_activities(2, TIMEOUT);
+    assert_eq!(activities.len(), 2);
+    assert_eq!(activities[1], Value::Null);
+}
+
+#[test]
+fn redundant_update_is_skipped() {
+    let mock = MockDiscord::start();
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+    manager.connect().unwrap();
+
+    manager.set_activity(&content("Playing Quest")).unwrap();
+    manager.set_activity(&content("Playing Quest")).unwrap();
+    manager.set_activity(&content("Playing Other")).unwrap();
+    manager.resend_activity(&content("Playing Other")).unwrap();
+
+    let activities = mock.wait_for_activities(3, TIMEOUT);
+    std::thread::sleep(Duration::from_millis(100));
+    let activities_after = mock.activities();
+    assert_eq!(activities.len(), 3);
+    assert_eq!(activities_after.len(), 3);
+    assert_eq!(activities_after[0]["details"], "Playing Quest");
+    assert_eq!(activities_after[1]["details"], "Playing Other");
+    assert_eq!(activities_after[2]["details"], "Playing Other");
+}
+
+#[test]
+fn worker_replays_activity_after_disconnect() {
+    let mock = MockDiscord::start();
+    let worker = RichPresenceWorker::spawn(CLIENT_ID, || {});
+    assert!(wait_for_status(&worker, PresenceStatus::Connected));
+
+    worker.set_activity(content("Playing Quest"));
+    assert_eq!(mock.wait_for_activities(1, TIMEOUT).len(), 1);
+
+    // La escritura falla al cortar la conexión y el hilo vuelve a conectar
+    mock.disconnect_clients();
+    worker.set_activity(content("Playing Other"));
+
+    let deadline = Instant::now() + TIMEOUT;
+    while mock.handshakes().len() < 2 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let activities = mock.wait_for_activities(2, TIMEOUT);
+    assert_eq!(mock.handshakes().len(), 2);
+    assert_eq!(activities.last().unwrap()["details"], "Playing Other");
+    assert!(wait_for_status(&worker, PresenceStatus::Connected));
+}
+
+#[test]
+fn worker_reconnects_after_discord_restart() {
+    let mut mock = MockDiscord::start();
+    let worker = RichPresenceWorker::spawn(CLIENT_ID, || {});
+    assert!(wait_for_status(&worker, PresenceStatus::Connected));
+    worker.set_activity(content("Playing Quest"));
+    assert_eq!(mock.wait_for_activities(1, TIMEOUT).len(), 1);
+
+    mock.stop();
+    worker.set_activity(content("Playing Other"));
+    assert!(wait_for_status(&worker, PresenceStatus::Disconnected));
+
+    // Igual que cuando la app detecta que Discord volvió a abrirse
+    mock.restart();
+    worker.reconnect();
+
+    assert!(wait_for_status(&worker, PresenceStatus::Connected));
+    let activities = mock.wait_for_activities(2, TIMEOUT);
+    assert_eq!(activities.len(), 2);
+    assert_eq!(activities[1]["details"], "Playing Other");
+}
+
+#[test]
+fn worker_shutdown_clears_activity() {
+    let mock = MockDiscord::start();
+    let mut worker = RichPresenceWorker::spawn(CLIENT_ID, || {});
+    assert!(wait_for_status(&worker, PresenceStatus::Connected));
+    worker.set_activity(content("Playing Quest"));
+    assert_eq!(mock.wait_for_activities(1, TIMEOUT).len(), 1);
+
+    worker.shutdown();
+
+    let activities = mock.wait_for_activities(2, TIMEOUT);
+    assert_eq!(activities.last(), Some(&Value::Null));
+    assert_eq!(worker.status(), PresenceStatus::Disconnected);
+}
+
+#[test]
+fn detection_finds_rich_presence_socket() {
+    let mock = MockDiscord::start();
+
+    let detection = detect_discord(&ClientRegistry::default(), &ProcessTable::default());
+
+    assert!(detection.is_running());
+    let socket = detection.rich_presence_socket().expect("mock IPC socket");
+    assert_eq!(socket.name(), "discord-ipc-0");
+
+    // Rich Presence se conecta al mismo socket
+    let mut manager = RichPresenceManager::new(CLIENT_ID);
+    manager.connect().unwrap();
+    assert_eq!(mock.handshakes().len(), 1);
+}
+
+/// Conexiones aceptadas una vez que el servidor atendió las pendientes
+fn settled_connections(mock: &MockDiscord) -> usize {
+    std::thread::sleep(Duration::from_millis(100));
+    mock.connections()
+}
+
+#[test]
+fn detection_connects_once_per_socket() {
+    let mut mock = MockDiscord::start();
+    let registry = ClientRegistry::default();
+    let table = ProcessTable::default();
+
+    assert_eq!(detect_discord(&registry, &table).sockets.len(), 1);
+    assert_eq!(detect_discord(&registry, &table).sockets.len(), 1);
+    assert_eq!(settled_connections(&mock), 1);
+
+    // Discord crea un socket nuevo al volver a abrirse
+    mock.restart();
+    assert_eq!(detect_discord(&registry, &table).sockets.len(), 1);
+    assert_eq!(settled_connections(&mock), 2);
+}
+
+#[test]
+fn detection_ignores_stale_socket() {
+    let mut mock = MockDiscord::start();
+    let path = std::path::PathBuf::from(std::env::var_os("XDG_RUNTIME_DIR").unwrap())
+        .join("discord-ipc-1");
+    mock.stop();
+
+    // El archivo queda pero nadie escucha
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    assert!(detect_discord(&ClientRegistry::default(), &ProcessTable::default()).sockets.is_empty());
+}
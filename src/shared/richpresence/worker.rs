@@ -0,0 +1,191 @@
+use super::client::RichPresenceManager;
+use super::template::ActivityContent;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Espera antes del primer reintento; se duplica hasta `MAX_BACKOFF`
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Cada cuánto se reenvía la actividad para notar si Discord se cerró
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub enum PresenceCommand {
+    SetActivity(ActivityContent),
+    Clear,
+    /// Descarta la conexión actual y vuelve a conectar de inmediato
+    Reconnect,
+    Shutdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresenceStatus {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+/// Hilo que mantiene la conexión con Discord. La interfaz solo envía
+/// comandos y nunca espera al IPC.
+pub struct RichPresenceWorker {
+    client_id: String,
+    sender: Sender<PresenceCommand>,
+    status: Arc<Mutex<PresenceStatus>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RichPresenceWorker {
+    /// Inicia el hilo. `notify` se llama cada vez que cambia el estado de la conexión.
+    pub fn spawn(client_id: &str, notify: impl Fn() + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let status = Arc::new(Mutex::new(PresenceStatus::Connecting));
+        let worker_status = Arc::clone(&status);
+        let worker_client_id = client_id.to_string();
+
+        let handle = std::thread::Builder::new()
+            .name("rich-presence".to_string())
+            .spawn(move || {
+                let set_status = |new_status: PresenceStatus| {
+                    if let Ok(mut status) = worker_status.lock() {
+                        if *status != new_status {
+                            *status = new_status;
+                            notify();
+                        }
+                    }
+                };
+                run(&worker_client_id, receiver, set_status);
+            })
+            .map_err(|e| log::error!("Failed to start Rich Presence thread: {}", e))
+            .ok();
+
+        Self {
+            client_id: client_id.to_string(),
+            sender,
+            status,
+            handle,
+        }
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub fn status(&self) -> PresenceStatus {
+        self.status
+            .lock()
+            .map(|status| *status)
+            .unwrap_or(PresenceStatus::Disconnected)
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.status() == PresenceStatus::Connected
+    }
+
+    pub fn set_activity(&self, content: ActivityContent) {
+        self.send(PresenceCommand::SetActivity(content));
+    }
+
+    pub fn reconnect(&self) {
+        self.send(PresenceCommand::Reconnect);
+    }
+
+    fn send(&self, command: PresenceCommand) {
+        if self.sender.send(command).is_err() {
+            log::warn!("Rich Presence thread is not running");
+        }
+    }
+
+    /// Borra la actividad, cierra la conexión y espera al hilo
+    pub fn shutdown(&mut self) {
+        let _ = self.sender.send(PresenceCommand::Clear);
+        let _ = self.sender.send(PresenceCommand::Shutdown);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for RichPresenceWorker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn run(
+    client_id: &str,
+    receiver: mpsc::Receiver<PresenceCommand>,
+    set_status: impl Fn(PresenceStatus),
+) {
+    let mut manager = RichPresenceManager::new(client_id);
+    // Actividad que debería verse; se vuelve a enviar al reconectar
+    let mut desired: Option<ActivityContent> = None;
+    let mut backoff = INITIAL_BACKOFF;
+    let mut next_attempt = Instant::now();
+    let mut last_sent = Instant::now();
+
+    loop {
+        if !manager.is_connected() && Instant::now() >= next_attempt {
+            set_status(PresenceStatus::Connecting);
+            match manager.connect() {
+                Ok(()) => {
+                    backoff = INITIAL_BACKOFF;
+                    if let Some(content) = &desired {
+                        let _ = manager.set_activity(content);
+                        last_sent = Instant::now();
+                    }
+                }
+                Err(_) => {
+                    log::info!("Retrying Rich Presence in {}s", backoff.as_secs());
+                    next_attempt = Instant::now() + backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+
+        set_status(if manager.is_connected() {
+            PresenceStatus::Connected
+        } else {
+            PresenceStatus::Disconnected
+        });
+
+        let timeout = if manager.is_connected() {
+            KEEPALIVE_INTERVAL.saturating_sub(last_sent.elapsed())
+        } else {
+            next_attempt.saturating_duration_since(Instant::now())
+        };
+
+        match receiver.recv_timeout(timeout) {
+            Ok(PresenceCommand::SetActivity(content)) => {
+                if manager.is_connected() {
+                    let _ = manager.set_activity(&content);
+                    last_sent = Instant::now();
+                }
+                desired = Some(content);
+            }
+            Ok(PresenceCommand::Clear) => {
+                desired = None;
+                let _ = manager.clear_activity();
+            }
+            Ok(PresenceCommand::Reconnect) => {
+                manager.disconnect();
+                backoff = INITIAL_BACKOFF;
+                next_attempt = Instant::now();
+            }
+            Ok(PresenceCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                // Reenviar la actividad detecta una conexión rota
+                if manager.is_connected() {
+                    if let Some(content) = &desired {
+                        let _ = manager.resend_activity(content);
+                    }
+                    last_sent = Instant::now();
+                }
+            }
+        }
+    }
+
+    manager.disconnect();
+    set_status(PresenceStatus::Disconnected);
+    log::debug!("Rich Presence thread stopped");
+}
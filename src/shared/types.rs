@@ -1,5 +1,8 @@
+use crate::core::hooks::SessionHook;
+use crate::core::scheduler::ScheduledRun;
+use crate::shared::richpresence::template::ActivityTemplate;
 use serde::{ Deserialize, Serialize };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub type LangMap = HashMap<String, String>;
 
@@ -10,6 +13,87 @@ pub struct Config {
     pub process_name: String,
     pub custom_path: String,
     pub rich_presence_enabled: bool,
+    #[serde(default)]
+    pub rich_presence_template: ActivityTemplate,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub rich_presence_client_id: String,
+    #[serde(default = "default_duration_minutes")]
+    pub default_duration_minutes: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<ScheduledRun>,
+    #[serde(default)]
+    pub auto_start: AutoStartRule,
+    /// Iniciar el preset seleccionado al terminar de abrir Discord desde la app
+    #[serde(default)]
+    pub launch_start_preset: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_preset: Option<String>,
+    #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<SessionHook>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_clients: Vec<CustomClient>,
+}
+
+/// Automatización al abrirse o cerrarse Discord
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AutoStartRule {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Preset a iniciar; si no hay, se usa el último utilizado
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub stop_on_discord_exit: bool,
+}
+
+/// Cliente de Discord agregado por el usuario, por ejemplo una
+/// instalación en otra carpeta o un cliente que no conocemos
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CustomClient {
+    pub name: String,
+    /// Nombres de proceso con los que se detecta
+    #[serde(default)]
+    pub process_names: Vec<String>,
+    /// Comando para abrirlo; vacío si solo se quiere detectar
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub launch_command: String,
+}
+
+/// API local para controlar la app desde scripts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_api_port")]
+    pub port: u16,
+    /// Si se indica, escucha en este socket Unix en lugar de HTTP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unix_socket: Option<String>,
+}
+
+pub const DEFAULT_API_PORT: u16 = 48150;
+
+fn default_api_port() -> u16 {
+    DEFAULT_API_PORT
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_API_PORT,
+            unix_socket: None,
+        }
+    }
+}
+
+/// Duración de simulación por defecto, en minutos
+pub const DEFAULT_DURATION_MINUTES: u64 = 15;
+
+fn default_duration_minutes() -> u64 {
+    DEFAULT_DURATION_MINUTES
 }
 
 impl Config {
@@ -20,15 +104,179 @@ impl Config {
             process_name: String::new(),
             custom_path: String::new(),
             rich_presence_enabled: true,
+            rich_presence_template: ActivityTemplate::default(),
+            rich_presence_client_id: String::new(),
+            default_duration_minutes: DEFAULT_DURATION_MINUTES,
+            schedules: Vec::new(),
+            auto_start: AutoStartRule::default(),
+            launch_start_preset: false,
+            last_used_preset: None,
+            api: ApiConfig::default(),
+            hooks: Vec::new(),
+            custom_clients: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct Preset {
     pub name: String,
+    pub executables: Vec<PresetExecutable>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Argumentos del proceso simulado; admiten plantillas como `{duration}`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Directorio de trabajo relativo a la ruta del preset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Tiempo de juego que pide la misión, si difiere del valor por defecto
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_minutes: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_custom: bool,
+}
+
+impl Preset {
+    /// Ejecutable principal: el primero con rol de juego, o el primero de la lista
+    pub fn primary(&self) -> Option<&PresetExecutable> {
+        self.executables
+            .iter()
+            .find(|exe| exe.role == ExecutableRole::Game)
+            .or_else(|| self.executables.first())
+    }
+
+    /// Indica si el preset incluye el ejecutable en la ruta dada (en cualquier sistema)
+    pub fn matches(&self, executable: &str, path: &str) -> bool {
+        self.executables.iter().any(|exe| {
+            HostOs::ALL.iter().filter_map(|os| exe.variant(*os)).any(|(e, p)| {
+                e.eq_ignore_ascii_case(executable) && p.eq_ignore_ascii_case(path)
+            })
+        })
+    }
+
+    /// Indica si todos los ejecutables tienen variante para el sistema actual
+    pub fn supports_host(&self) -> bool {
+        let host = HostOs::current();
+        self.executables.iter().all(|exe| exe.variant(host).is_some())
+    }
+
+    /// Indica si el preset tiene todas las etiquetas dadas
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| {
+            let tag = tag.trim().to_lowercase();
+            self.tags.iter().any(|t| t.trim().to_lowercase() == tag)
+        })
+    }
+
+    /// Ejecutables resueltos para el sistema actual
+    pub fn host_executables(&self) -> Vec<PresetExecutable> {
+        self.executables.iter().map(|exe| exe.for_host()).collect()
+    }
+}
+
+/// Sistema operativo para el que se declara una variante de ejecutable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostOs {
+    Windows,
+    Linux,
+    MacOs,
+}
+
+impl HostOs {
+    pub const ALL: [HostOs; 3] = [HostOs::Windows, HostOs::Linux, HostOs::MacOs];
+
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            HostOs::MacOs
+        } else if cfg!(windows) {
+            HostOs::Windows
+        } else {
+            HostOs::Linux
+        }
+    }
+}
+
+/// `executable` y `path` corresponden a Windows; `linux` y `macos`
+/// declaran los nombres para esos sistemas
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct PresetExecutable {
     pub executable: String,
     pub path: String,
-    #[serde(default)]
-    pub is_custom: bool,
+    #[serde(default, skip_serializing_if = "ExecutableRole::is_default")]
+    pub role: ExecutableRole,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linux: Option<ExecutableVariant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub macos: Option<ExecutableVariant>,
+}
+
+/// Nombre y ruta de un ejecutable en otro sistema. Sin ruta, usa la de Windows.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ExecutableVariant {
+    pub executable: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl PresetExecutable {
+    /// Nombre y ruta declarados para un sistema, si existen
+    pub fn variant(&self, os: HostOs) -> Option<(&str, &str)> {
+        let variant = match os {
+            HostOs::Windows => return Some((&self.executable, &self.path)),
+            HostOs::Linux => self.linux.as_ref(),
+            HostOs::MacOs => self.macos.as_ref(),
+        }?;
+        Some((
+            &variant.executable,
+            variant.path.as_deref().unwrap_or(&self.path),
+        ))
+    }
+
+    /// Copia con el nombre y ruta del sistema actual. Si no hay variante
+    /// se conservan los de Windows.
+    pub fn for_host(&self) -> PresetExecutable {
+        let (executable, path) = self
+            .variant(HostOs::current())
+            .unwrap_or((&self.executable, &self.path));
+        PresetExecutable {
+            executable: executable.to_string(),
+            path: path.to_string(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Función de cada ejecutable dentro de un preset
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutableRole {
+    #[default]
+    Game,
+    Launcher,
+    Alternate,
+}
+
+impl ExecutableRole {
+    pub const ALL: [ExecutableRole; 3] = [
+        ExecutableRole::Game,
+        ExecutableRole::Launcher,
+        ExecutableRole::Alternate,
+    ];
+
+    fn is_default(&self) -> bool {
+        *self == ExecutableRole::Game
+    }
+
+    pub fn lang_key(&self) -> &'static str {
+        match self {
+            ExecutableRole::Game => "role_game",
+            ExecutableRole::Launcher => "role_launcher",
+            ExecutableRole::Alternate => "role_alternate",
+        }
+    }
 }
@@ -0,0 +1,41 @@
+//! Utilidades para las pruebas que leen y escriben archivos relativos
+//! a la carpeta actual (`config.json`, `presets_custom.json`, ...).
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// La carpeta actual es del proceso, así que estas pruebas no pueden
+/// correr a la vez
+static CWD_LOCK: Mutex<()> = Mutex::new(());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Restaura la carpeta anterior aunque la prueba falle
+struct RestoreCwd {
+    previous: PathBuf,
+    dir: PathBuf,
+}
+
+impl Drop for RestoreCwd {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.previous);
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Ejecuta `f` dentro de una carpeta temporal vacía
+pub fn in_temp_dir<R>(f: impl FnOnce(&Path) -> R) -> R {
+    let _lock = CWD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = std::env::temp_dir().join(format!(
+        "dsq-test-{}-{}",
+        std::process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir).expect("create test directory");
+    let _restore = RestoreCwd {
+        previous: std::env::current_dir().expect("current directory"),
+        dir: dir.clone(),
+    };
+    std::env::set_current_dir(&dir).expect("enter test directory");
+    f(&dir)
+}